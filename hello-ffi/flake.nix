@@ -0,0 +1,129 @@
+{
+  description = "hello-ffi - C ABI for hello-rs";
+
+  inputs = {
+    nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+    flake-utils.url = "github:numtide/flake-utils";
+
+    # Source-only input - we just need the Rust code, not the flake outputs
+    hello-rs.url = "git+file:///Users/matt/src/hello-subflakes/subflake-git/hello-rs?ref=main";
+    hello-rs.flake = false;
+
+    poag = {
+      url = "path:../poag";
+      inputs.nixpkgs.follows = "nixpkgs";
+    };
+  };
+
+  outputs = { self, nixpkgs, flake-utils, hello-rs, poag }:
+    flake-utils.lib.eachDefaultSystem (system:
+      let
+        pkgs = import nixpkgs {
+          inherit system;
+        };
+
+        # Create source with hello-rs included from non-flake input
+        helloFfiSrc = pkgs.runCommand "hello-ffi-src" {} ''
+          mkdir -p $out
+          cp -r ${./.}/* $out/
+          chmod -R +w $out
+          mkdir -p $out/hello-rs
+          cp -r ${hello-rs}/* $out/hello-rs/
+          # Update the Cargo.toml path to point to ./hello-rs instead of ../hello-rs
+          sed -i 's|path = "../hello-rs"|path = "./hello-rs"|' $out/Cargo.toml
+        '';
+
+        # The shared and static libraries plus the C header
+        helloFfi = pkgs.stdenv.mkDerivation {
+          pname = "hello-ffi";
+          version = "0.1.0";
+          src = helloFfiSrc;
+
+          cargoDeps = pkgs.rustPlatform.importCargoLock {
+            lockFile = ./Cargo.lock;
+          };
+
+          nativeBuildInputs = with pkgs; [
+            cargo
+            rustc
+            rustPlatform.cargoSetupHook
+          ];
+
+          buildPhase = ''
+            cargo build --release --offline
+          '';
+
+          installPhase = ''
+            mkdir -p $out/lib $out/include
+            cp target/release/libhello_ffi.a $out/lib/
+            cp target/release/libhello_ffi${pkgs.stdenv.hostPlatform.extensions.sharedLibrary} $out/lib/
+            cp include/hello.h $out/include/
+          '';
+        };
+      in
+      {
+        packages = {
+          default = helloFfi;
+          hello-ffi = helloFfi;
+        };
+
+        checks = {
+          # The committed header must match what cbindgen generates from
+          # src/lib.rs, so consumers never build against a stale one
+          header = pkgs.runCommand "hello-ffi-header" {
+            nativeBuildInputs = [ pkgs.rust-cbindgen ];
+          } ''
+            cp -r ${helloFfiSrc} src
+            chmod -R +w src
+            cd src
+            cbindgen --config cbindgen.toml --output generated.h
+            diff -u include/hello.h generated.h
+            mkdir -p $out
+            echo "include/hello.h is up to date" > $out/result
+          '';
+
+          # Compile the C test program against the installed header and
+          # static library, then run it
+          c-test = pkgs.stdenv.mkDerivation {
+            name = "hello-ffi-c-test";
+            src = ./tests;
+
+            buildPhase = ''
+              $CC -Wall -Wextra -Werror test_hello.c \
+                -I${helloFfi}/include ${helloFfi}/lib/libhello_ffi.a \
+                -lpthread -ldl -lm -o test_hello
+              ./test_hello 2>&1 | tee test-output.txt
+            '';
+
+            installPhase = ''
+              mkdir -p $out
+              cp test-output.txt $out/
+            '';
+          };
+        };
+
+        devShells.default = pkgs.mkShell {
+          packages = with pkgs; [
+            cargo
+            rustc
+            rust-analyzer
+            clippy
+            rustfmt
+            rust-cbindgen
+          ];
+          buildInputs = [
+            poag.packages.${system}.default
+          ];
+          shellHook = ''
+            export REPO_ROOT=$(pwd)
+            echo "hello-ffi development environment"
+            echo ""
+            echo "To regenerate the C header:"
+            echo "  cbindgen --config cbindgen.toml --output include/hello.h"
+            echo ""
+            echo "To build and test:"
+            echo "  nix flake check"
+          '';
+        };
+      });
+}
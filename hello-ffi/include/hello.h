@@ -0,0 +1,142 @@
+/* C interface to hello-rs. Generated by cbindgen from src/lib.rs; do not edit. */
+
+#ifndef HELLO_H
+#define HELLO_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The outcome of a call. Anything other than `HELLO_STATUS_OK` means the
+ * out-parameter was set to NULL.
+ *
+ * Statuses are `int32_t` in C, so their size doesn't depend on the compiler
+ * and newer versions of this library can add statuses without breaking the
+ * ABI.
+ */
+enum HelloStatus
+#if defined(__cplusplus) || __STDC_VERSION__ >= 202311L
+  : int32_t
+#endif // defined(__cplusplus) || __STDC_VERSION__ >= 202311L
+ {
+  /**
+   * The call succeeded
+   */
+  HELLO_STATUS_OK = 0,
+  /**
+   * A required pointer argument was NULL
+   */
+  HELLO_STATUS_NULL_ARGUMENT = 1,
+  /**
+   * A string argument was not valid UTF-8
+   */
+  HELLO_STATUS_INVALID_UTF8 = 2,
+  /**
+   * The locale is not a valid language tag
+   */
+  HELLO_STATUS_INVALID_LOCALE = 3,
+  /**
+   * There is no greeting style with the given name
+   */
+  HELLO_STATUS_UNKNOWN_STYLE = 4,
+  /**
+   * The name is empty or only whitespace
+   */
+  HELLO_STATUS_EMPTY_NAME = 5,
+  /**
+   * The name is longer than the name policy allows
+   */
+  HELLO_STATUS_NAME_TOO_LONG = 6,
+  /**
+   * The name contains control characters such as newlines
+   */
+  HELLO_STATUS_CONTROL_CHARACTERS = 7,
+  /**
+   * The name contains bidi embeddings, overrides or isolates
+   */
+  HELLO_STATUS_UNSAFE_BIDI = 8,
+  /**
+   * The name mixes confusable scripts, e.g. Latin and Cyrillic
+   */
+  HELLO_STATUS_INVALID_SCRIPT_MIX = 9,
+};
+#ifndef __cplusplus
+#if __STDC_VERSION__ >= 202311L
+typedef enum HelloStatus HelloStatus;
+#else
+typedef int32_t HelloStatus;
+#endif // __STDC_VERSION__ >= 202311L
+#endif // __cplusplus
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Greet someone: "hello {name}"
+ *
+ * On success `*out` is set to a new string that must be released with
+ * `hello_string_free`.
+ *
+ * # Safety
+ *
+ * `name` must be NULL or point to a NUL-terminated string, and `out` must
+ * be NULL or point to writable storage for a pointer.
+ */
+HelloStatus hello_greet(const char *name, char **out);
+
+/**
+ * Greet someone after checking their name against hello-rs's default name
+ * policy, e.g. rejecting empty names and bidi overrides
+ *
+ * On success `*out` is set to a new string that must be released with
+ * `hello_string_free`.
+ *
+ * # Safety
+ *
+ * `name` must be NULL or point to a NUL-terminated string, and `out` must
+ * be NULL or point to writable storage for a pointer.
+ */
+HelloStatus hello_try_greet(const char *name, char **out);
+
+/**
+ * Greet someone in a named style ("plain", "fancy", "formal", "shout",
+ * "varied", "given", "family" or "full") and a locale such as "pt-BR"
+ *
+ * `locale` may be NULL for English. On success `*out` is set to a new
+ * string that must be released with `hello_string_free`.
+ *
+ * # Safety
+ *
+ * `style`, `locale` and `name` must each be NULL or point to a
+ * NUL-terminated string, and `out` must be NULL or point to writable
+ * storage for a pointer.
+ */
+HelloStatus hello_greet_style(const char *style, const char *locale, const char *name, char **out);
+
+/**
+ * Release a string returned by this library. Passing NULL does nothing.
+ *
+ * # Safety
+ *
+ * `s` must be NULL or a string returned by this library that has not
+ * already been freed.
+ */
+void hello_string_free(char *s);
+
+/**
+ * A static, human-readable description of `status`. Do not free it.
+ *
+ * Any value is accepted; one that isn't a known `HelloStatus`, such as a
+ * status from a newer header, is described as "unknown status".
+ */
+const char *hello_status_message(int32_t status);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* HELLO_H */
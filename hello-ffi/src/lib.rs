@@ -0,0 +1,326 @@
+//! C ABI for hello-rs
+//!
+//! This crate builds hello-rs as a shared and a static library with a C
+//! interface, described by the generated header `include/hello.h`, so that
+//! C, C++ and Go (through cgo) can greet people the same way the Python and
+//! WebAssembly bindings do.
+//!
+//! # Ownership
+//!
+//! - Strings passed in are borrowed for the duration of the call. They must
+//!   be NUL-terminated UTF-8 and may be freed as soon as the call returns.
+//! - Functions that produce a string return a [`HelloStatus`] and write the
+//!   string to an out-parameter. On `HELLO_STATUS_OK` the string is owned by
+//!   the caller, who must release it with exactly one call to
+//!   [`hello_string_free`]. On any other status the out-parameter is set to
+//!   NULL and there is nothing to free.
+//! - Strings returned directly, such as [`hello_status_message`], are
+//!   static and must not be freed.
+//!
+//! Every function is safe to call from several threads at once.
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use hello_rs::{HelloError, Locale, StyleRegistry};
+
+thread_local! {
+    /// hello-rs's built-in greeting styles, built once per thread
+    static STYLES: StyleRegistry = StyleRegistry::default();
+}
+
+/// The outcome of a call. Anything other than `HELLO_STATUS_OK` means the
+/// out-parameter was set to NULL.
+///
+/// Statuses are `int32_t` in C, so their size doesn't depend on the compiler
+/// and newer versions of this library can add statuses without breaking the
+/// ABI.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelloStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A required pointer argument was NULL
+    NullArgument = 1,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// The locale is not a valid language tag
+    InvalidLocale = 3,
+    /// There is no greeting style with the given name
+    UnknownStyle = 4,
+    /// The name is empty or only whitespace
+    EmptyName = 5,
+    /// The name is longer than the name policy allows
+    NameTooLong = 6,
+    /// The name contains control characters such as newlines
+    ControlCharacters = 7,
+    /// The name contains bidi embeddings, overrides or isolates
+    UnsafeBidi = 8,
+    /// The name mixes confusable scripts, e.g. Latin and Cyrillic
+    InvalidScriptMix = 9,
+}
+
+impl HelloStatus {
+    /// Every status, in order
+    const ALL: [HelloStatus; 10] = [
+        HelloStatus::Ok,
+        HelloStatus::NullArgument,
+        HelloStatus::InvalidUtf8,
+        HelloStatus::InvalidLocale,
+        HelloStatus::UnknownStyle,
+        HelloStatus::EmptyName,
+        HelloStatus::NameTooLong,
+        HelloStatus::ControlCharacters,
+        HelloStatus::UnsafeBidi,
+        HelloStatus::InvalidScriptMix,
+    ];
+
+    /// The status with the discriminant `status`, if there is one
+    fn from_raw(status: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| *s as i32 == status)
+    }
+}
+
+impl From<HelloError> for HelloStatus {
+    fn from(error: HelloError) -> Self {
+        match error {
+            HelloError::Empty => HelloStatus::EmptyName,
+            HelloError::TooLong { .. } => HelloStatus::NameTooLong,
+            HelloError::ControlCharacters { .. } => HelloStatus::ControlCharacters,
+            HelloError::UnsafeBidi { .. } => HelloStatus::UnsafeBidi,
+            HelloError::InvalidScriptMix { .. } => HelloStatus::InvalidScriptMix,
+        }
+    }
+}
+
+/// Greet someone: "hello {name}"
+///
+/// On success `*out` is set to a new string that must be released with
+/// `hello_string_free`.
+///
+/// # Safety
+///
+/// `name` must be NULL or point to a NUL-terminated string, and `out` must
+/// be NULL or point to writable storage for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn hello_greet(name: *const c_char, out: *mut *mut c_char) -> HelloStatus {
+    respond(out, || Ok(hello_rs::hello(borrow(name)?)))
+}
+
+/// Greet someone after checking their name against hello-rs's default name
+/// policy, e.g. rejecting empty names and bidi overrides
+///
+/// On success `*out` is set to a new string that must be released with
+/// `hello_string_free`.
+///
+/// # Safety
+///
+/// `name` must be NULL or point to a NUL-terminated string, and `out` must
+/// be NULL or point to writable storage for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn hello_try_greet(
+    name: *const c_char,
+    out: *mut *mut c_char,
+) -> HelloStatus {
+    respond(out, || Ok(hello_rs::try_hello(borrow(name)?)?))
+}
+
+/// Greet someone in a named style ("plain", "fancy", "formal", "shout",
+/// "varied", "given", "family" or "full") and a locale such as "pt-BR"
+///
+/// `locale` may be NULL for English. On success `*out` is set to a new
+/// string that must be released with `hello_string_free`.
+///
+/// # Safety
+///
+/// `style`, `locale` and `name` must each be NULL or point to a
+/// NUL-terminated string, and `out` must be NULL or point to writable
+/// storage for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn hello_greet_style(
+    style: *const c_char,
+    locale: *const c_char,
+    name: *const c_char,
+    out: *mut *mut c_char,
+) -> HelloStatus {
+    respond(out, || {
+        let style = borrow(style)?;
+        let locale = if locale.is_null() {
+            Locale::default()
+        } else {
+            Locale::parse(borrow(locale)?).map_err(|_| HelloStatus::InvalidLocale)?
+        };
+        let name = borrow(name)?;
+        STYLES.with(|styles| {
+            styles
+                .greet(style, &locale, name)
+                .map_err(|_| HelloStatus::UnknownStyle)
+        })
+    })
+}
+
+/// Release a string returned by this library. Passing NULL does nothing.
+///
+/// # Safety
+///
+/// `s` must be NULL or a string returned by this library that has not
+/// already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn hello_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// A static, human-readable description of `status`. Do not free it.
+///
+/// Any value is accepted; one that isn't a known `HelloStatus`, such as a
+/// status from a newer header, is described as "unknown status".
+#[no_mangle]
+pub extern "C" fn hello_status_message(status: i32) -> *const c_char {
+    let message: &'static CStr = match HelloStatus::from_raw(status) {
+        Some(HelloStatus::Ok) => c"ok",
+        Some(HelloStatus::NullArgument) => c"a required argument was NULL",
+        Some(HelloStatus::InvalidUtf8) => c"a string argument is not valid UTF-8",
+        Some(HelloStatus::InvalidLocale) => c"invalid locale tag",
+        Some(HelloStatus::UnknownStyle) => c"unknown greeting style",
+        Some(HelloStatus::EmptyName) => c"name is empty",
+        Some(HelloStatus::NameTooLong) => c"name is too long",
+        Some(HelloStatus::ControlCharacters) => c"name contains control characters",
+        Some(HelloStatus::UnsafeBidi) => c"name contains bidi control characters",
+        Some(HelloStatus::InvalidScriptMix) => c"name mixes confusable scripts",
+        None => c"unknown status",
+    };
+    message.as_ptr()
+}
+
+/// Borrow a C string argument as UTF-8
+///
+/// # Safety
+///
+/// `s` must be NULL or point to a NUL-terminated string that outlives 'a.
+unsafe fn borrow<'a>(s: *const c_char) -> Result<&'a str, HelloStatus> {
+    if s.is_null() {
+        return Err(HelloStatus::NullArgument);
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|_| HelloStatus::InvalidUtf8)
+}
+
+/// Run `f` and hand its greeting to the caller through `out`
+///
+/// # Safety
+///
+/// `out` must be NULL or point to writable storage for a pointer.
+unsafe fn respond(
+    out: *mut *mut c_char,
+    f: impl FnOnce() -> Result<String, HelloStatus>,
+) -> HelloStatus {
+    if out.is_null() {
+        return HelloStatus::NullArgument;
+    }
+    *out = ptr::null_mut();
+    match f() {
+        Ok(greeting) => {
+            // The inputs were C strings, so the greeting has no NUL bytes
+            let greeting = CString::new(greeting).expect("greetings contain no NUL bytes");
+            *out = greeting.into_raw();
+            HelloStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Call `f` with an out-parameter and take ownership of the result
+    fn call(f: impl FnOnce(*mut *mut c_char) -> HelloStatus) -> Result<String, HelloStatus> {
+        let mut out = ptr::dangling_mut();
+        let status = f(&mut out);
+        if status != HelloStatus::Ok {
+            assert!(out.is_null());
+            return Err(status);
+        }
+        let greeting = unsafe { CStr::from_ptr(out) }.to_str().unwrap().to_owned();
+        unsafe { hello_string_free(out) };
+        Ok(greeting)
+    }
+
+    #[test]
+    fn test_greet() {
+        assert_eq!(
+            call(|out| unsafe { hello_greet(c"world".as_ptr(), out) }),
+            Ok("hello world".into())
+        );
+        assert_eq!(
+            call(|out| unsafe { hello_greet(ptr::null(), out) }),
+            Err(HelloStatus::NullArgument)
+        );
+        let invalid = b"\xff\0";
+        assert_eq!(
+            call(|out| unsafe { hello_greet(invalid.as_ptr().cast(), out) }),
+            Err(HelloStatus::InvalidUtf8)
+        );
+        assert_eq!(
+            unsafe { hello_greet(c"world".as_ptr(), ptr::null_mut()) },
+            HelloStatus::NullArgument
+        );
+    }
+
+    #[test]
+    fn test_try_greet() {
+        assert_eq!(
+            call(|out| unsafe { hello_try_greet(c"ana".as_ptr(), out) }),
+            Ok("hello ana".into())
+        );
+        assert_eq!(
+            call(|out| unsafe { hello_try_greet(c" ".as_ptr(), out) }),
+            Err(HelloStatus::EmptyName)
+        );
+        assert_eq!(
+            call(|out| unsafe { hello_try_greet(c"ana\u{202e}bo".as_ptr(), out) }),
+            Err(HelloStatus::UnsafeBidi)
+        );
+    }
+
+    #[test]
+    fn test_greet_style() {
+        let greet = |style: &CStr, locale: Option<&CStr>, name: &CStr| {
+            let locale = locale.map_or(ptr::null(), CStr::as_ptr);
+            call(|out| unsafe { hello_greet_style(style.as_ptr(), locale, name.as_ptr(), out) })
+        };
+        assert_eq!(greet(c"fancy", None, c"ana"), Ok("Hello Ana!".into()));
+        assert_eq!(
+            greet(c"shout", Some(c"de"), c"ana"),
+            Ok("HALLO ANA!".into())
+        );
+        assert_eq!(
+            greet(c"whisper", None, c"ana"),
+            Err(HelloStatus::UnknownStyle)
+        );
+        assert_eq!(
+            greet(c"plain", Some(c"not a tag"), c"ana"),
+            Err(HelloStatus::InvalidLocale)
+        );
+    }
+
+    #[test]
+    fn test_status_message() {
+        let message = |status| unsafe { CStr::from_ptr(hello_status_message(status)) };
+        assert_eq!(
+            message(HelloStatus::UnknownStyle as i32),
+            c"unknown greeting style"
+        );
+        for status in [10, -1, i32::MAX, i32::MIN] {
+            assert_eq!(message(status), c"unknown status");
+        }
+        for (i, status) in HelloStatus::ALL.into_iter().enumerate() {
+            assert_eq!(status as i32, i as i32);
+            assert_ne!(message(status as i32), c"unknown status");
+        }
+        unsafe { hello_string_free(ptr::null_mut()) };
+    }
+}
@@ -0,0 +1,93 @@
+/* Exercises the C interface through include/hello.h, the way a C or C++
+ * service would. Built and run by the c-test check in flake.nix:
+ *
+ *   cc tests/test_hello.c -Iinclude target/release/libhello_ffi.a -lpthread -ldl -lm
+ */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "hello.h"
+
+static int failures = 0;
+
+/* Check that a call succeeded with `expected`, then free the greeting */
+static void expect_greeting(const char *test, HelloStatus status, char *greeting,
+                            const char *expected) {
+  if (status != HELLO_STATUS_OK) {
+    printf("FAIL %s: %s\n", test, hello_status_message(status));
+    failures++;
+  } else if (strcmp(greeting, expected) != 0) {
+    printf("FAIL %s: expected \"%s\", got \"%s\"\n", test, expected, greeting);
+    failures++;
+  } else {
+    printf("ok   %s\n", test);
+  }
+  hello_string_free(greeting);
+}
+
+/* Check that a call failed with `expected` and left nothing to free */
+static void expect_status(const char *test, HelloStatus status, char *greeting,
+                          HelloStatus expected) {
+  if (status != expected || greeting != NULL) {
+    printf("FAIL %s: expected \"%s\", got \"%s\"\n", test,
+           hello_status_message(expected), hello_status_message(status));
+    failures++;
+  } else {
+    printf("ok   %s\n", test);
+  }
+}
+
+int main(void) {
+  char *greeting;
+  HelloStatus status;
+
+  status = hello_greet("world", &greeting);
+  expect_greeting("hello_greet", status, greeting, "hello world");
+
+  status = hello_greet(NULL, &greeting);
+  expect_status("hello_greet_null", status, greeting, HELLO_STATUS_NULL_ARGUMENT);
+
+  status = hello_greet("\xff", &greeting);
+  expect_status("hello_greet_invalid_utf8", status, greeting, HELLO_STATUS_INVALID_UTF8);
+
+  status = hello_try_greet("ana", &greeting);
+  expect_greeting("hello_try_greet", status, greeting, "hello ana");
+
+  status = hello_try_greet("", &greeting);
+  expect_status("hello_try_greet_empty", status, greeting, HELLO_STATUS_EMPTY_NAME);
+
+  status = hello_try_greet("ana\nbo", &greeting);
+  expect_status("hello_try_greet_control", status, greeting,
+                HELLO_STATUS_CONTROL_CHARACTERS);
+
+  status = hello_greet_style("fancy", NULL, "anne-marie van der berg", &greeting);
+  expect_greeting("hello_greet_style", status, greeting, "Hello Anne-Marie van der Berg!");
+
+  status = hello_greet_style("shout", "de", "ana", &greeting);
+  expect_greeting("hello_greet_style_locale", status, greeting, "HALLO ANA!");
+
+  status = hello_greet_style("fancy", "tr", "istanbul", &greeting);
+  expect_greeting("hello_greet_style_utf8", status, greeting, "Merhaba \xc4\xb0stanbul!");
+
+  status = hello_greet_style("whisper", NULL, "ana", &greeting);
+  expect_status("hello_greet_style_unknown", status, greeting, HELLO_STATUS_UNKNOWN_STYLE);
+
+  status = hello_greet_style("plain", "not a tag", "ana", &greeting);
+  expect_status("hello_greet_style_bad_locale", status, greeting,
+                HELLO_STATUS_INVALID_LOCALE);
+
+  hello_string_free(NULL);
+
+  /* A status this header doesn't know, e.g. from a newer version */
+  const char *message = hello_status_message(1000);
+  if (strcmp(message, "unknown status") != 0) {
+    printf("FAIL hello_status_message_unknown: got \"%s\"\n", message);
+    failures++;
+  } else {
+    printf("ok   hello_status_message_unknown\n");
+  }
+
+  printf("%s\n", failures == 0 ? "all tests passed" : "some tests failed");
+  return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,23 @@
+// Build the addon with cargo and copy it to hello_node.node, where index.js
+// loads it from. Pass --debug for an unoptimized build; any other arguments,
+// such as --offline, are passed on to cargo.
+'use strict'
+
+const { execFileSync } = require('node:child_process')
+const { copyFileSync } = require('node:fs')
+const { join } = require('node:path')
+
+const args = process.argv.slice(2)
+const debug = args.includes('--debug')
+const cargoArgs = args.filter((arg) => arg !== '--debug')
+execFileSync('cargo', ['build', ...(debug ? [] : ['--release']), ...cargoArgs], {
+  cwd: __dirname,
+  stdio: 'inherit',
+})
+
+const library = {
+  darwin: 'libhello_node.dylib',
+  win32: 'hello_node.dll',
+}[process.platform] ?? 'libhello_node.so'
+const profile = debug ? 'debug' : 'release'
+copyFileSync(join(__dirname, 'target', profile, library), join(__dirname, 'hello_node.node'))
@@ -0,0 +1,116 @@
+{
+  description = "hello-node - Node.js bindings for hello-rs";
+
+  inputs = {
+    nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+    flake-utils.url = "github:numtide/flake-utils";
+
+    # Source-only input - we just need the Rust code, not the flake outputs
+    hello-rs.url = "git+file:///Users/matt/src/hello-subflakes/subflake-git/hello-rs?ref=main";
+    hello-rs.flake = false;
+
+    poag = {
+      url = "path:../poag";
+      inputs.nixpkgs.follows = "nixpkgs";
+    };
+  };
+
+  outputs = { self, nixpkgs, flake-utils, hello-rs, poag }:
+    flake-utils.lib.eachDefaultSystem (system:
+      let
+        pkgs = import nixpkgs {
+          inherit system;
+        };
+
+        # Create source with hello-rs included from non-flake input
+        helloNodeSrc = pkgs.runCommand "hello-node-src" {} ''
+          mkdir -p $out
+          cp -r ${./.}/* $out/
+          chmod -R +w $out
+          mkdir -p $out/hello-rs
+          cp -r ${hello-rs}/* $out/hello-rs/
+          # Update the Cargo.toml path to point to ./hello-rs instead of ../hello-rs
+          sed -i 's|path = "../hello-rs"|path = "./hello-rs"|' $out/Cargo.toml
+        '';
+
+        # The addon plus the JavaScript entry point and typings, laid out
+        # as an npm package
+        helloNode = pkgs.stdenv.mkDerivation {
+          pname = "hello-node";
+          version = "0.1.0";
+          src = helloNodeSrc;
+
+          cargoDeps = pkgs.rustPlatform.importCargoLock {
+            lockFile = ./Cargo.lock;
+          };
+
+          nativeBuildInputs = with pkgs; [
+            cargo
+            rustc
+            nodejs
+            rustPlatform.cargoSetupHook
+          ];
+
+          buildPhase = ''
+            node build.js --offline
+          '';
+
+          installPhase = ''
+            mkdir -p $out/lib/node_modules/hello-node
+            cp package.json index.js index.d.ts hello_node.node $out/lib/node_modules/hello-node/
+          '';
+        };
+      in
+      {
+        packages = {
+          default = helloNode;
+          hello-node = helloNode;
+        };
+
+        checks = {
+          # Run the node tests against the built addon, replaying hello-rs's
+          # golden vectors from the same source the addon was built from
+          node-test = pkgs.stdenv.mkDerivation {
+            name = "hello-node-test";
+            src = ./.;
+
+            nativeBuildInputs = [ pkgs.nodejs ];
+
+            buildPhase = ''
+              export HELLO_NODE_ADDON=${helloNode}/lib/node_modules/hello-node/hello_node.node
+              export HELLO_RS_VECTORS=${hello-rs}/vectors/greetings.json
+              node --test tests/ 2>&1 | tee test-output.txt
+            '';
+
+            installPhase = ''
+              mkdir -p $out
+              cp test-output.txt $out/
+            '';
+          };
+        };
+
+        devShells.default = pkgs.mkShell {
+          packages = with pkgs; [
+            cargo
+            rustc
+            rust-analyzer
+            clippy
+            rustfmt
+            nodejs
+          ];
+          buildInputs = [
+            poag.packages.${system}.default
+          ];
+          shellHook = ''
+            export REPO_ROOT=$(pwd)
+            echo "hello-node development environment"
+            echo ""
+            echo "To build the addon and run the tests:"
+            echo "  npm run build && npm test"
+            echo ""
+            echo "Or:"
+            echo "  nix flake check"
+          '';
+        };
+      });
+}
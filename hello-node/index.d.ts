@@ -0,0 +1,133 @@
+/** Node.js bindings for hello-rs. */
+
+/** How formal a greeting sounds. */
+export type Formality = 'intimate' | 'casual' | 'neutral' | 'formal' | 'ceremonial'
+
+/** How a greeting is rendered. */
+export type Format = 'plain' | 'html' | 'markdown' | 'ansi' | 'json'
+
+/**
+ * How someone likes to be greeted, in the schema hello-rs documents for its
+ * GreetingConfig. Every key is optional.
+ */
+export interface GreetingConfig {
+  /** A language tag such as "pt-BR", "en" by default */
+  locale?: string
+  /** "neutral" by default */
+  formality?: Formality
+  capitalization?: 'as_is' | 'lower' | 'upper' | 'sentence' | 'title'
+  /** Punctuation after the name, "" by default */
+  punctuation?: string
+  /** The recipient's grammatical gender, which `welcome` agrees with */
+  gender?: 'feminine' | 'masculine' | 'neutral'
+  /** Welcome the recipient instead of greeting them, e.g. "bienvenida Ana" */
+  welcome?: boolean
+  /** Follow the greeting with "how are you?" */
+  how_are_you?: boolean
+  /** Whether and how the name is isolated from the rest of the greeting */
+  bidi?: { isolate?: boolean; strip_controls?: boolean } | null
+  /** A template such as "{salutation|title}, {name}{punct}" */
+  template?: string | null
+  /** How the greeting is rendered when there is no template */
+  format?: Format
+}
+
+/** Options for {@link greet}. */
+export interface GreetOptions {
+  /** A language tag such as "pt-BR", English by default */
+  locale?: string
+  /** Wrap names containing right-to-left text in Unicode isolates */
+  isolateBidi?: boolean
+  /** Remove bidi overrides from the name */
+  stripBidiControls?: boolean
+}
+
+/** Options for {@link helloWithFormality}. */
+export interface FormalityOptions {
+  /** A language tag such as "pt-BR", English by default */
+  locale?: string
+  /** Follow the greeting with "how are you?" */
+  howAreYou?: boolean
+  format?: Format
+}
+
+/** Why {@link tryHello} rejected a name, found in the thrown Error's `code`. */
+export type HelloErrorCode =
+  | 'EMPTY_NAME'
+  | 'NAME_TOO_LONG'
+  | 'CONTROL_CHARACTERS'
+  | 'UNSAFE_BIDI'
+  | 'INVALID_SCRIPT_MIX'
+
+/** Greet someone: "hello {name}" */
+export declare function hello(name: string): string
+
+/** Greet several people at once, e.g. "hello Ana, Bo, and Cy" */
+export declare function helloAll(names: string[], locale?: string): string
+
+/**
+ * Greet someone after validating their name.
+ *
+ * Throws an Error whose `code` is a {@link HelloErrorCode}.
+ */
+export declare function tryHello(name: string): string
+
+/** Greet someone in the given locale, e.g. "pt-BR" */
+export declare function helloIn(locale: string, name: string): string
+
+/**
+ * Greet someone in a named style: "plain" (the default), "fancy", "formal",
+ * "shout", "varied", "given", "family" or "full".
+ *
+ * Throws for an unknown style or locale.
+ */
+export declare function greet(name: string, style?: string, options?: GreetOptions): string
+
+/**
+ * Greet a person at a formality level, "neutral" by default.
+ *
+ * Throws for an unknown formality, format or locale.
+ */
+export declare function helloWithFormality(
+  name: string,
+  formality?: Formality,
+  options?: FormalityOptions,
+): string
+
+/**
+ * Greet someone as described by a config.
+ *
+ * Throws if the config doesn't match the schema.
+ */
+export declare function helloWithConfig(name: string, config: GreetingConfig): string
+
+/** The names of the styles {@link greet} accepts */
+export declare function styles(): string[]
+
+/** The locale tags that hello-rs ships translations for */
+export declare function supportedLocales(): string[]
+
+/** Title-case text the way hello-rs does, e.g. "o'neill" -> "O'Neill" */
+export declare function titleCase(text: string, locale?: string): string
+
+/** A greeting for one person, built from a {@link GreetingConfig}. */
+export declare class Greeting {
+  /**
+   * Greet `name`, parsed into honorific, given and family names.
+   *
+   * Throws if the config doesn't match the schema.
+   */
+  constructor(name: string, config?: GreetingConfig)
+  /** The salutation, e.g. "good day" */
+  get salutation(): string
+  /** How the person is addressed, e.g. "Dr. Okafor" */
+  get recipient(): string
+  /** The locale tag, e.g. "pt-BR" */
+  get locale(): string
+  /** The formality level, e.g. "formal" */
+  get formality(): Formality
+  /** The greeting rendered in `format`, "plain" by default */
+  render(format?: Format): string
+  /** The greeting rendered as the config describes */
+  toString(): string
+}
@@ -0,0 +1,7 @@
+// Load the native addon built by build.js. HELLO_NODE_ADDON overrides the
+// path, e.g. to load an addon built by Nix.
+'use strict'
+
+const { join } = require('node:path')
+
+module.exports = require(process.env.HELLO_NODE_ADDON ?? join(__dirname, 'hello_node.node'))
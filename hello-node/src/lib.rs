@@ -0,0 +1,259 @@
+//! Node.js bindings for hello-rs
+//!
+//! A native N-API addon exposing the same greetings as hello-py, with
+//! camelCase names. Typings for the JavaScript surface are in index.d.ts.
+
+use std::cell::RefCell;
+
+use hello_rs::format::TitleCase;
+use hello_rs::{
+    BidiIsolation, Formality, Format, Greeting as RsGreeting, GreetingConfig, Locale, PersonName,
+    Render, StyleRegistry,
+};
+use napi::bindgen_prelude::*;
+use napi::JsString;
+use napi_derive::napi;
+
+thread_local! {
+    /// Scratch space that greetings are rendered into and copied straight
+    /// into a JavaScript string from, so repeated calls don't allocate a
+    /// String each
+    static BUFFER: RefCell<String> = const { RefCell::new(String::new()) };
+
+    /// hello-rs's built-in greeting styles, built once per thread
+    static STYLES: StyleRegistry = StyleRegistry::default();
+}
+
+/// Greet someone: "hello {name}"
+#[napi]
+pub fn hello(env: Env, name: String) -> Result<JsString> {
+    BUFFER.with_borrow_mut(|buf| env.create_string(hello_rs::hello_into(buf, &name)))
+}
+
+/// Greet several people at once, e.g. "hello Ana, Bo, and Cy"
+#[napi]
+pub fn hello_all(env: Env, names: Vec<String>, locale: Option<String>) -> Result<JsString> {
+    let locale = parse_locale(locale.as_deref())?;
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    BUFFER.with_borrow_mut(|buf| {
+        buf.clear();
+        hello_rs::write_hello_all_in(buf, &locale, &names)
+            .expect("writing to a String cannot fail");
+        env.create_string(buf)
+    })
+}
+
+/// Greet someone after validating their name
+///
+/// Throws an Error whose `code` says why the name was rejected.
+#[napi]
+pub fn try_hello(name: String) -> Result<String, &'static str> {
+    hello_rs::try_hello(&name).map_err(hello_error)
+}
+
+/// Greet someone in the given locale, e.g. "pt-BR"
+#[napi]
+pub fn hello_in(locale: String, name: String) -> Result<String> {
+    let locale = parse_locale(Some(&locale))?;
+    Ok(hello_rs::hello_in(&locale, &name))
+}
+
+/// Options for [`greet`]
+#[napi(object)]
+#[derive(Default)]
+pub struct GreetOptions {
+    /// A language tag such as "pt-BR", English by default
+    pub locale: Option<String>,
+    /// Wrap names containing right-to-left text in Unicode isolates
+    pub isolate_bidi: Option<bool>,
+    /// Remove bidi overrides from the name
+    pub strip_bidi_controls: Option<bool>,
+}
+
+/// Greet someone in a named style, "plain" by default
+#[napi]
+pub fn greet(name: String, style: Option<String>, options: Option<GreetOptions>) -> Result<String> {
+    let options = options.unwrap_or_default();
+    let locale = parse_locale(options.locale.as_deref())?;
+    let name = BidiIsolation::new()
+        .isolate(options.isolate_bidi.unwrap_or(false))
+        .strip_controls(options.strip_bidi_controls.unwrap_or(false))
+        .apply(&name);
+    STYLES.with(|styles| {
+        styles
+            .greet(style.as_deref().unwrap_or("plain"), &locale, &name)
+            .map_err(invalid_arg)
+    })
+}
+
+/// Options for [`hello_with_formality`]
+#[napi(object)]
+#[derive(Default)]
+pub struct FormalityOptions {
+    /// A language tag such as "pt-BR", English by default
+    pub locale: Option<String>,
+    /// Follow the greeting with "how are you?"
+    pub how_are_you: Option<bool>,
+    /// "plain", "html", "markdown", "ansi" or "json"
+    pub format: Option<String>,
+}
+
+/// Greet a person at a formality level, "neutral" by default
+#[napi]
+pub fn hello_with_formality(
+    name: String,
+    formality: Option<String>,
+    options: Option<FormalityOptions>,
+) -> Result<String> {
+    let options = options.unwrap_or_default();
+    let locale = parse_locale(options.locale.as_deref())?;
+    let formality: Formality = formality
+        .as_deref()
+        .unwrap_or("neutral")
+        .parse()
+        .map_err(|e: hello_rs::FormalityError| invalid_arg(e))?;
+    let format: Format = options
+        .format
+        .as_deref()
+        .unwrap_or("plain")
+        .parse()
+        .map_err(|e: hello_rs::FormatError| invalid_arg(e))?;
+    let person = PersonName::parse(&name, &locale);
+    let greeting = RsGreeting::for_person(person)
+        .formality(formality)
+        .locale(locale)
+        .how_are_you(options.how_are_you.unwrap_or(false))
+        .build();
+    Ok(format.render(&greeting))
+}
+
+/// Greet someone as described by a config object in the hello-rs config
+/// schema, e.g. `{ locale: "de", formality: "formal" }`
+#[napi(ts_args_type = "name: string, config: GreetingConfig")]
+pub fn hello_with_config(name: String, config: serde_json::Value) -> Result<String> {
+    Ok(parse_config(config)?.greet(&name))
+}
+
+/// The names of the styles `greet` accepts
+#[napi]
+pub fn styles() -> Vec<String> {
+    STYLES.with(|styles| styles.names().map(str::to_string).collect())
+}
+
+/// The locale tags that hello-rs ships translations for
+#[napi]
+pub fn supported_locales() -> Vec<String> {
+    hello_rs::supported_locales()
+        .map(|locale| locale.to_string())
+        .collect()
+}
+
+/// Title-case text the way hello-rs does, e.g. "o'neill" -> "O'Neill"
+#[napi]
+pub fn title_case(text: String, locale: Option<String>) -> Result<String> {
+    let title_case = match locale {
+        Some(locale) => TitleCase::for_locale(&parse_locale(Some(&locale))?),
+        None => TitleCase::new(),
+    };
+    Ok(title_case.apply(&text))
+}
+
+/// A greeting for one person, built from a config in the hello-rs config
+/// schema
+#[napi]
+pub struct Greeting {
+    config: GreetingConfig,
+    name: String,
+    greeting: RsGreeting,
+}
+
+#[napi]
+impl Greeting {
+    /// Greet `name`, parsed into honorific, given and family names
+    #[napi(constructor, ts_args_type = "name: string, config?: GreetingConfig")]
+    pub fn new(name: String, config: Option<serde_json::Value>) -> Result<Self> {
+        let config = match config {
+            Some(config) => parse_config(config)?,
+            None => GreetingConfig::default(),
+        };
+        let greeting = config.greeting(&name);
+        Ok(Greeting {
+            config,
+            name,
+            greeting,
+        })
+    }
+
+    /// The salutation, e.g. "good day"
+    #[napi(getter)]
+    pub fn salutation(&self) -> String {
+        self.greeting.salutation().to_owned()
+    }
+
+    /// How the person is addressed, e.g. "Dr. Okafor"
+    #[napi(getter)]
+    pub fn recipient(&self) -> String {
+        self.greeting.recipient().to_owned()
+    }
+
+    /// The locale tag, e.g. "pt-BR"
+    #[napi(getter)]
+    pub fn locale(&self) -> String {
+        self.greeting.locale().to_string()
+    }
+
+    /// The formality level, e.g. "formal"
+    #[napi(getter)]
+    pub fn formality(&self) -> String {
+        self.greeting.formality().as_str().to_owned()
+    }
+
+    /// The greeting rendered in `format`: "plain", "html", "markdown",
+    /// "ansi" or "json"
+    #[napi(ts_args_type = "format?: Format")]
+    pub fn render(&self, format: Option<String>) -> Result<String> {
+        let format: Format = format
+            .as_deref()
+            .unwrap_or("plain")
+            .parse()
+            .map_err(|e: hello_rs::FormatError| invalid_arg(e))?;
+        Ok(format.render(&self.greeting))
+    }
+
+    /// The greeting rendered as the config describes
+    #[napi(js_name = "toString")]
+    pub fn to_js_string(&self) -> String {
+        self.config.greet(&self.name)
+    }
+}
+
+/// Convert a hello-rs error into a JavaScript Error whose `code` names it
+fn hello_error(error: hello_rs::HelloError) -> Error<&'static str> {
+    let code = match error {
+        hello_rs::HelloError::Empty => "EMPTY_NAME",
+        hello_rs::HelloError::TooLong { .. } => "NAME_TOO_LONG",
+        hello_rs::HelloError::ControlCharacters { .. } => "CONTROL_CHARACTERS",
+        hello_rs::HelloError::UnsafeBidi { .. } => "UNSAFE_BIDI",
+        hello_rs::HelloError::InvalidScriptMix { .. } => "INVALID_SCRIPT_MIX",
+    };
+    Error::new(code, error.to_string())
+}
+
+/// A JavaScript Error with code "InvalidArg" carrying `error`'s message
+fn invalid_arg(error: impl ToString) -> Error {
+    Error::new(Status::InvalidArg, error.to_string())
+}
+
+/// Parse an optional language tag, defaulting to English
+fn parse_locale(locale: Option<&str>) -> Result<Locale> {
+    locale
+        .map(Locale::parse)
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(invalid_arg)
+}
+
+/// Read a config object in the schema documented on hello-rs's GreetingConfig
+fn parse_config(config: serde_json::Value) -> Result<GreetingConfig> {
+    serde_json::from_value(config).map_err(invalid_arg)
+}
@@ -0,0 +1,133 @@
+// Tests for the Node.js bindings, run with `node --test tests/` after
+// `node build.js`.
+'use strict'
+
+const assert = require('node:assert/strict')
+const fs = require('node:fs')
+const path = require('node:path')
+const { describe, test } = require('node:test')
+
+const hello = require('..')
+
+// The shared golden vectors from hello-rs, replayed against these bindings
+const VECTOR_FILE =
+  process.env.HELLO_RS_VECTORS ?? path.join(__dirname, '..', '..', 'hello-rs', 'vectors', 'greetings.json')
+
+test('hello', () => {
+  assert.equal(hello.hello('world'), 'hello world')
+  assert.equal(hello.hello(''), 'hello ')
+})
+
+test('helloAll', () => {
+  assert.equal(hello.helloAll(['ana', 'bo', 'cy']), 'hello ana, bo, and cy')
+  assert.throws(() => hello.helloAll(['ana'], 'not a tag'), { code: 'InvalidArg' })
+})
+
+test('tryHello', () => {
+  assert.equal(hello.tryHello('ana'), 'hello ana')
+  assert.throws(() => hello.tryHello(''), { code: 'EMPTY_NAME', message: 'name is empty' })
+  assert.throws(() => hello.tryHello('ana\nbo'), { code: 'CONTROL_CHARACTERS' })
+  assert.throws(() => hello.tryHello('ana‮bo'), { code: 'UNSAFE_BIDI' })
+  assert.throws(() => hello.tryHello('a'.repeat(1000)), { code: 'NAME_TOO_LONG' })
+})
+
+test('helloIn', () => {
+  assert.equal(hello.helloIn('pt-BR', 'ana'), 'olá ana')
+  assert.throws(() => hello.helloIn('not a tag', 'ana'), { code: 'InvalidArg' })
+})
+
+describe('greet', () => {
+  test('styles', () => {
+    assert.equal(hello.greet('ana'), 'hello ana')
+    assert.equal(hello.greet('anne-marie van der berg', 'fancy'), 'Hello Anne-Marie van der Berg!')
+    assert.equal(hello.greet('ana', 'shout', { locale: 'de' }), 'HALLO ANA!')
+    assert.equal(hello.greet('ana', 'varied'), 'greetings ana')
+  })
+
+  test('bidi', () => {
+    assert.equal(hello.greet('ana‮bo', 'plain', { stripBidiControls: true }), 'hello anabo')
+    assert.equal(hello.greet('דוד', 'plain', { isolateBidi: true }), 'hello ⁨דוד⁩')
+  })
+
+  test('unknown style', () => {
+    assert.throws(() => hello.greet('ana', 'whisper'), { code: 'InvalidArg' })
+  })
+
+  test('style names', () => {
+    assert.deepEqual(hello.styles(), ['plain', 'fancy', 'formal', 'shout', 'varied', 'given', 'family', 'full'])
+  })
+})
+
+test('helloWithFormality', () => {
+  assert.equal(hello.helloWithFormality('Dr. Chidi Okafor', 'formal'), 'good day, Dr. Okafor')
+  assert.equal(
+    hello.helloWithFormality('Dr. Chidi Okafor', 'formal', { howAreYou: true }),
+    'good day, Dr. Okafor, how are you?',
+  )
+  assert.throws(() => hello.helloWithFormality('ana', 'stiff'), { code: 'InvalidArg' })
+  assert.throws(() => hello.helloWithFormality('ana', 'formal', { format: 'pdf' }), { code: 'InvalidArg' })
+})
+
+test('helloWithConfig', () => {
+  assert.equal(hello.helloWithConfig('ana', { locale: 'de', formality: 'formal' }), 'guten Tag, ana')
+  assert.equal(hello.helloWithConfig('ana', { locale: 'es', gender: 'feminine', welcome: true }), 'bienvenida ana')
+  assert.throws(() => hello.helloWithConfig('ana', { formalty: 'formal' }), { code: 'InvalidArg' })
+})
+
+test('supportedLocales', () => {
+  const locales = hello.supportedLocales()
+  assert.ok(locales.includes('en'))
+  assert.ok(locales.includes('pt-BR'))
+})
+
+test('titleCase', () => {
+  assert.equal(hello.titleCase("o'neill"), "O'Neill")
+  assert.equal(hello.titleCase('istanbul', 'tr'), 'İstanbul')
+})
+
+describe('Greeting', () => {
+  test('parts', () => {
+    const greeting = new hello.Greeting('Dr. Chidi Okafor', { formality: 'formal', locale: 'pt-BR' })
+    assert.equal(greeting.salutation, 'cumprimentos')
+    assert.equal(greeting.recipient, 'Dr. Okafor')
+    assert.equal(greeting.locale, 'pt-BR')
+    assert.equal(greeting.formality, 'formal')
+  })
+
+  test('render', () => {
+    const greeting = new hello.Greeting('Dr. Chidi Okafor', { formality: 'formal', locale: 'pt-BR' })
+    assert.equal(greeting.render(), 'cumprimentos, Dr. Okafor')
+    assert.equal(greeting.render('html'), '<span lang="pt-BR">cumprimentos, <bdi>Dr. Okafor</bdi></span>')
+    assert.deepEqual(JSON.parse(greeting.render('json')), {
+      text: 'cumprimentos, Dr. Okafor',
+      salutation: 'cumprimentos',
+      recipient: 'Dr. Okafor',
+      locale: 'pt-BR',
+      formality: 'formal',
+    })
+    assert.throws(() => greeting.render('pdf'), { code: 'InvalidArg' })
+  })
+
+  test('toString', () => {
+    assert.equal(String(new hello.Greeting('ana')), hello.helloWithConfig('ana', {}))
+    assert.equal(`${new hello.Greeting('ana', { locale: 'de', formality: 'formal' })}`, 'guten Tag, ana')
+  })
+
+  test('invalid config', () => {
+    assert.throws(() => new hello.Greeting('ana', { formalty: 'formal' }), { code: 'InvalidArg' })
+  })
+})
+
+describe('golden vectors', () => {
+  const file = JSON.parse(fs.readFileSync(VECTOR_FILE, 'utf8'))
+
+  test('version', () => {
+    assert.equal(file.version, 1)
+  })
+
+  for (const vector of file.vectors) {
+    test(`${vector.style} ${vector.locale} ${JSON.stringify(vector.name)}`, () => {
+      assert.equal(hello.greet(vector.name, vector.style, { locale: vector.locale }), vector.expected)
+    })
+  }
+})
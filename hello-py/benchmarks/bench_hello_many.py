@@ -0,0 +1,79 @@
+"""Compare hello_many() with calling hello() once per name.
+
+Run with the extension installed, e.g. from the dev shell:
+
+    python benchmarks/bench_hello_many.py [--repeat N]
+
+Each measurement is the best of `--repeat` runs, and speedups are relative
+to the per-call loop. The last table has several Python threads greeting
+batches at once, which is where releasing the GIL matters.
+"""
+
+import argparse
+import os
+import threading
+import timeit
+
+from hello_py import hello, hello_many
+
+SIZES = [1_000, 100_000, 1_000_000]
+CPUS = os.cpu_count() or 1
+
+
+def best_of(repeat, func):
+    """The fastest of `repeat` runs of `func`, in seconds."""
+    return min(timeit.repeat(func, number=1, repeat=repeat))
+
+
+def in_threads(count, func):
+    """A function that runs `func` on `count` Python threads at once."""
+
+    def run():
+        workers = [threading.Thread(target=func) for _ in range(count)]
+        for worker in workers:
+            worker.start()
+        for worker in workers:
+            worker.join()
+
+    return run
+
+
+def report(label, repeat, loop, candidates):
+    """Time the loop and each candidate and print one row."""
+    baseline = best_of(repeat, loop)
+    row = f"{label:>10}  {baseline * 1e3:>10.2f} ms"
+    for candidate in candidates:
+        elapsed = best_of(repeat, candidate)
+        row += f"  {elapsed * 1e3:>8.2f} ms {baseline / elapsed:>5.1f}x"
+    print(row)
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("--repeat", type=int, default=5, help="runs per measurement")
+    args = parser.parse_args()
+
+    print(f"{'names':>10}  {'hello() loop':>13}  {'hello_many':>17}  {f'threads={CPUS}':>17}")
+    for size in SIZES:
+        names = [f"name {i}" for i in range(size)]
+        report(
+            f"{size:,}",
+            args.repeat,
+            lambda: [hello(name) for name in names],
+            [lambda: hello_many(names), lambda: hello_many(names, threads=0)],
+        )
+
+    print()
+    print(f"{'py threads':>10}  {'hello() loop':>13}  {'hello_many':>17}")
+    names = [f"name {i}" for i in range(100_000)]
+    for count in sorted({2, CPUS}):
+        report(
+            f"{count} x 100k",
+            args.repeat,
+            in_threads(count, lambda: [hello(name) for name in names]),
+            [in_threads(count, lambda: hello_many(names))],
+        )
+
+
+if __name__ == "__main__":
+    main()
@@ -25,6 +25,10 @@
 
         python = pkgs.python312;
 
+        # The free-threaded (no GIL) build of CPython, which hello-py's
+        # extension declares support for
+        pythonFreeThreaded = pkgs.python313FreeThreading;
+
         # Filter to exclude build artifacts and dev directories
         helloPySrc = builtins.path {
           path = ./.;
@@ -47,30 +51,33 @@
         # Reference hello-rs source from the non-flake input
         helloRsSrc = hello-rs;
 
+        # The crate source with hello-rs copied in next to it
+        helloPyCrateSrc = pkgs.runCommand "hello-py-src" {} ''
+          mkdir -p $out
+          # Copy hello-py source files (filtered to exclude build artifacts)
+          cp -r ${helloPySrc}/* $out/
+          chmod -R +w $out
+
+          # Copy hello-rs source (referenced via builtins.path)
+          cp -r ${helloRsSrc} $out/hello-rs
+          chmod -R +w $out/hello-rs
+
+          # Update the Cargo.toml path to point to ./hello-rs instead of ../hello-rs
+          sed -i 's|path = "../hello-rs"|path = "./hello-rs"|' $out/Cargo.toml
+
+          # Update flake.lock to also use ./hello-rs instead of ../hello-rs
+          # This prevents Nix from trying to resolve ../hello-rs during the build
+          if [ -f $out/flake.lock ]; then
+            sed -i 's|"path": "../hello-rs"|"path": "./hello-rs"|g' $out/flake.lock
+          fi
+        '';
+
         # Build the wheel using maturin
         # Need to include hello-rs source for the build
         helloPyWheel = pkgs.stdenv.mkDerivation {
           pname = "hello-py-wheel";
           version = "0.1.0";
-          src = pkgs.runCommand "hello-py-src" {} ''
-            mkdir -p $out
-            # Copy hello-py source files (filtered to exclude build artifacts)
-            cp -r ${helloPySrc}/* $out/
-            chmod -R +w $out
-
-            # Copy hello-rs source (referenced via builtins.path)
-            cp -r ${helloRsSrc} $out/hello-rs
-            chmod -R +w $out/hello-rs
-
-            # Update the Cargo.toml path to point to ./hello-rs instead of ../hello-rs
-            sed -i 's|path = "../hello-rs"|path = "./hello-rs"|' $out/Cargo.toml
-
-            # Update flake.lock to also use ./hello-rs instead of ../hello-rs
-            # This prevents Nix from trying to resolve ../hello-rs during the build
-            if [ -f $out/flake.lock ]; then
-              sed -i 's|"path": "../hello-rs"|"path": "./hello-rs"|g' $out/flake.lock
-            fi
-          '';
+          src = helloPyCrateSrc;
 
           cargoDeps = pkgs.rustPlatform.importCargoLock {
             lockFile = ./Cargo.lock;
@@ -95,6 +102,16 @@
           '';
         };
 
+        # The same wheel built for free-threaded Python, which doesn't
+        # support abi3 and so needs an interpreter-specific build
+        helloPyWheelFreeThreaded = helloPyWheel.overrideAttrs (_: {
+          pname = "hello-py-wheel-free-threaded";
+          buildPhase = ''
+            maturin build --release --offline --compatibility off --out dist \
+              --interpreter ${pythonFreeThreaded.interpreter}
+          '';
+        });
+
         # Create a Python package from the wheel
         helloPyPackage = python.pkgs.buildPythonPackage {
           pname = "hello_py";
@@ -120,6 +137,31 @@
           '';
         };
 
+        # Run hello-rs's contract against the hello-rs this crate is built with
+        contractCheck = pkgs.stdenv.mkDerivation {
+          name = "hello-py-contract";
+          src = helloPyCrateSrc;
+
+          cargoDeps = pkgs.rustPlatform.importCargoLock {
+            lockFile = ./Cargo.lock;
+          };
+
+          nativeBuildInputs = with pkgs; [
+            rustPlatform.cargoSetupHook
+            cargo
+            rustc
+          ];
+
+          buildPhase = ''
+            cargo test --offline --test contract 2>&1 | tee test-output.txt
+          '';
+
+          installPhase = ''
+            mkdir -p $out
+            cp test-output.txt $out/
+          '';
+        };
+
         # Pure test environment with hello-py and pytest
         testEnv = python.withPackages (ps: [
           helloPyPackage
@@ -133,6 +175,8 @@
         } ''
           export HOME=$TMPDIR
           export PYTHONDONTWRITEBYTECODE=1
+          # The golden vectors shared with hello-rs and hello-wasm
+          export HELLO_RS_VECTORS=${helloRsSrc}/vectors/greetings.json
 
           # Copy tests to build directory (Nix store is read-only)
           cp -r ${./tests} ./tests
@@ -167,6 +211,28 @@
           echo "All tests passed" > $out/result
         '';
 
+        # Run the tests on free-threaded Python, including the check that
+        # importing hello-py leaves the GIL disabled
+        freeThreadedTestEnv = pythonFreeThreaded.withPackages (ps: [ ps.pytest ]);
+
+        pytestFreeThreadedCheck = pkgs.runCommand "hello-py-pytest-free-threaded" {
+          buildInputs = [ freeThreadedTestEnv ];
+        } ''
+          export HOME=$TMPDIR
+          export PYTHONDONTWRITEBYTECODE=1
+          export HELLO_RS_VECTORS=${helloRsSrc}/vectors/greetings.json
+
+          mkdir site
+          ${pkgs.unzip}/bin/unzip -q ${helloPyWheelFreeThreaded}/hello_py.whl -d site
+          cp -r ${./tests} ./tests
+          chmod -R +w ./tests
+
+          mkdir $out
+          set -o pipefail
+          PYTHONPATH=$PWD/site ${freeThreadedTestEnv}/bin/python -m pytest ./tests -v --tb=short \
+            | tee $out/summary.txt
+        '';
+
       in
       {
         packages = {
@@ -179,6 +245,8 @@
         checks = {
           # This runs with `nix flake check`
           pytest = pytestCheck;
+          pytest-free-threaded = pytestFreeThreadedCheck;
+          contract = contractCheck;
         };
 
         devShells.default = pkgs.mkShell {
@@ -206,6 +274,9 @@
             echo "To run tests:"
             echo "  pytest tests/ -v"
             echo ""
+            echo "To compare hello_many() with a hello() loop:"
+            echo "  python benchmarks/bench_hello_many.py"
+            echo ""
             echo "To rebuild and test:"
             echo "  nix flake check"
           '';
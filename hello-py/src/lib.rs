@@ -1,18 +1,936 @@
+use std::cell::RefCell;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::sync::{LazyLock, RwLock};
+use std::thread;
+
+use hello_rs::format::TitleCase;
+use hello_rs::{
+    BidiIsolation, FixedClock, Formality, Format, Greeter, GreetingConfig, PersonName, Render,
+    StyleRegistry, SystemClock, UtcOffset, VariedGreeter, ZonedTime,
+};
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyString, PyTuple};
+
+create_exception!(
+    _rust,
+    HelloError,
+    PyValueError,
+    "A name was rejected by hello-rs's name policy"
+);
+create_exception!(
+    _rust,
+    EmptyNameError,
+    HelloError,
+    "The name is empty or only whitespace"
+);
+create_exception!(_rust, NameTooLongError, HelloError, "The name is too long");
+create_exception!(
+    _rust,
+    ControlCharactersError,
+    HelloError,
+    "The name contains control characters"
+);
+create_exception!(
+    _rust,
+    UnsafeBidiError,
+    HelloError,
+    "The name contains bidi control characters"
+);
+create_exception!(
+    _rust,
+    InvalidScriptMixError,
+    HelloError,
+    "The name mixes confusable scripts"
+);
+create_exception!(
+    _rust,
+    TemplateError,
+    PyValueError,
+    "A greeting template could not be compiled"
+);
+
+thread_local! {
+    /// Scratch space that greetings are rendered into before being copied
+    /// into a Python str, so repeated calls don't allocate a String each
+    static BUFFER: RefCell<String> = const { RefCell::new(String::new()) };
+}
 
-// Import the Rust library
-use hello_rs;
+/// The styles greet() can use: hello-rs's built-ins plus any added with
+/// register_style()
+static STYLES: LazyLock<RwLock<StyleRegistry>> = LazyLock::new(Default::default);
+
+/// Run `f` with this thread's cleared scratch buffer
+fn with_buffer<R>(f: impl FnOnce(&mut String) -> R) -> R {
+    BUFFER.with_borrow_mut(|buf| {
+        buf.clear();
+        f(buf)
+    })
+}
 
 /// A Python wrapper around the Rust hello function
 #[pyfunction]
-fn hello(name: &str) -> String {
-    hello_rs::hello(name)
+fn hello<'py>(py: Python<'py>, name: &str) -> Bound<'py, PyString> {
+    with_buffer(|buf| PyString::new(py, hello_rs::hello_into(buf, name)))
+}
+
+/// Greet every name in `names`, any iterable of str, returning a list of
+/// greetings in the same order
+///
+/// The greetings are built with the GIL released, so other Python threads
+/// keep running meanwhile. `threads` > 1 splits large batches across that
+/// many OS threads, and 0 uses one thread per CPU.
+#[pyfunction]
+#[pyo3(signature = (names, *, threads=1))]
+fn hello_many<'py>(
+    py: Python<'py>,
+    names: &Bound<'py, PyAny>,
+    threads: usize,
+) -> PyResult<Bound<'py, PyList>> {
+    let mut packed = Packed::default();
+    for name in names.try_iter()? {
+        let name = name?;
+        packed.push(name.downcast::<PyString>()?.to_str()?);
+    }
+    let batches = py.allow_threads(|| greet_batches(&packed, threads));
+    let greetings: Vec<_> = batches
+        .iter()
+        .flat_map(Packed::iter)
+        .map(|greeting| PyString::new(py, greeting))
+        .collect();
+    PyList::new(py, greetings)
+}
+
+/// Below this many names per thread, spawning threads costs more than it
+/// saves
+const MIN_NAMES_PER_THREAD: usize = 4096;
+
+/// Greet `names` on up to `threads` threads (0 for one per CPU), returning
+/// one batch of greetings per thread
+fn greet_batches(names: &Packed, threads: usize) -> Vec<Packed> {
+    let greet = |range: Range<usize>| {
+        let len = names.text_len(range.clone()) + range.len() * "hello ".len();
+        let mut greetings = Packed::with_capacity(range.len(), len);
+        for name in names.slice(range) {
+            greetings.push_with(|text| {
+                hello_rs::write_hello(text, name).expect("writing to a String cannot fail")
+            });
+        }
+        greetings
+    };
+    let len = names.ends.len();
+    let threads = match threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    let threads = threads.min(len / MIN_NAMES_PER_THREAD).max(1);
+    if threads == 1 {
+        return vec![greet(0..len)];
+    }
+    let chunk = len.div_ceil(threads);
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..len)
+            .step_by(chunk)
+            .map(|start| scope.spawn(move || greet(start..len.min(start + chunk))))
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("greeting a name cannot panic"))
+            .collect()
+    })
+}
+
+/// Strings written back to back into one buffer, so a batch of names or
+/// greetings takes two allocations rather than one per string
+#[derive(Default)]
+struct Packed {
+    text: String,
+    ends: Vec<usize>,
+}
+
+impl Packed {
+    /// Room for `count` strings totalling `len` bytes
+    fn with_capacity(count: usize, len: usize) -> Self {
+        Packed {
+            text: String::with_capacity(len),
+            ends: Vec::with_capacity(count),
+        }
+    }
+
+    fn push(&mut self, s: &str) {
+        self.push_with(|text| text.push_str(s));
+    }
+
+    /// Add the string that `write` appends to the buffer
+    fn push_with(&mut self, write: impl FnOnce(&mut String)) {
+        write(&mut self.text);
+        self.ends.push(self.text.len());
+    }
+
+    fn start(&self, index: usize) -> usize {
+        index.checked_sub(1).map_or(0, |i| self.ends[i])
+    }
+
+    /// The total length in bytes of the strings in `range`
+    fn text_len(&self, range: Range<usize>) -> usize {
+        if range.is_empty() {
+            return 0;
+        }
+        self.ends[range.end - 1] - self.start(range.start)
+    }
+
+    /// The strings in `range`
+    fn slice(&self, range: Range<usize>) -> impl Iterator<Item = &str> {
+        let starts = std::iter::once(self.start(range.start))
+            .chain(self.ends[range.clone()].iter().copied());
+        starts
+            .zip(&self.ends[range])
+            .map(|(start, &end)| &self.text[start..end])
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &str> {
+        self.slice(0..self.ends.len())
+    }
+}
+
+/// Greet several people at once, e.g. "hello Ana, Bo, and Cy"
+///
+/// Names are joined the way `locale` (English by default) writes lists, and
+/// very long lists end with "and N others".
+#[pyfunction]
+#[pyo3(signature = (names, locale=None))]
+fn hello_all<'py>(
+    py: Python<'py>,
+    names: Vec<String>,
+    locale: Option<LocaleArg>,
+) -> PyResult<Bound<'py, PyString>> {
+    let locale = locale_or_default(locale)?;
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    with_buffer(|buf| {
+        hello_rs::write_hello_all_in(buf, &locale, &names)
+            .expect("writing to a String cannot fail");
+        Ok(PyString::new(py, buf))
+    })
+}
+
+/// Greet someone after validating their name
+///
+/// Raises a subclass of HelloError describing why the name was rejected.
+#[pyfunction]
+fn try_hello(name: &str) -> PyResult<String> {
+    hello_rs::try_hello(name).map_err(hello_error)
+}
+
+/// Convert a hello-rs error into the matching Python exception
+fn hello_error(error: hello_rs::HelloError) -> PyErr {
+    let message = error.to_string();
+    match error {
+        hello_rs::HelloError::Empty => EmptyNameError::new_err(message),
+        hello_rs::HelloError::TooLong { .. } => NameTooLongError::new_err(message),
+        hello_rs::HelloError::ControlCharacters { .. } => ControlCharactersError::new_err(message),
+        hello_rs::HelloError::UnsafeBidi { .. } => UnsafeBidiError::new_err(message),
+        hello_rs::HelloError::InvalidScriptMix { .. } => InvalidScriptMixError::new_err(message),
+    }
+}
+
+/// Greet someone in the given locale, e.g. "pt-BR"
+///
+/// Raises ValueError if `locale` is not a valid language tag.
+#[pyfunction]
+fn hello_in(locale: LocaleArg, name: &str) -> PyResult<String> {
+    let locale = locale.parse()?;
+    Ok(hello_rs::hello_in(&locale, name))
+}
+
+/// Greet someone for the time of day, e.g. "good morning Ana"
+///
+/// The time is read from the system clock unless `timestamp` (seconds since
+/// the Unix epoch) is given, and is interpreted `utc_offset_minutes` east of
+/// UTC, clamped to ±18 hours. Holidays such as Christmas and Easter get their
+/// own salutation.
+#[pyfunction]
+#[pyo3(signature = (name, *, locale=None, utc_offset_minutes=0, timestamp=None))]
+fn hello_now(
+    name: &str,
+    locale: Option<LocaleArg>,
+    utc_offset_minutes: i32,
+    timestamp: Option<i64>,
+) -> PyResult<String> {
+    let locale = locale_or_default(locale)?;
+    let offset = UtcOffset::from_minutes(utc_offset_minutes);
+    Ok(match timestamp {
+        Some(seconds) => {
+            let clock = FixedClock(ZonedTime::new(seconds, offset));
+            hello_rs::hello_now(&clock, &locale, name)
+        }
+        None => hello_rs::hello_now(&SystemClock::new(offset), &locale, name),
+    })
+}
+
+/// Greet someone in a named style: "plain", "fancy", "formal", "shout",
+/// "varied", "given", "family", "full", or one added with register_style()
+///
+/// styles() lists the styles available.
+///
+/// With isolate_bidi=True a name containing right-to-left text is wrapped in
+/// Unicode isolates so it can't reorder the rest of the greeting, and
+/// strip_bidi_controls=True removes any bidi overrides the name contains.
+///
+/// Raises ValueError for an unknown style or locale.
+#[pyfunction]
+#[pyo3(signature = (
+    name,
+    style="plain",
+    *,
+    locale=None,
+    isolate_bidi=false,
+    strip_bidi_controls=false,
+))]
+fn greet(
+    name: &str,
+    style: &str,
+    locale: Option<LocaleArg>,
+    isolate_bidi: bool,
+    strip_bidi_controls: bool,
+) -> PyResult<String> {
+    let locale = locale_or_default(locale)?;
+    let name = BidiIsolation::new()
+        .isolate(isolate_bidi)
+        .strip_controls(strip_bidi_controls)
+        .apply(name);
+    let styles = STYLES.read().unwrap_or_else(|e| e.into_inner());
+    styles
+        .greet(style, &locale, &name)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Greet someone with one of the locale's salutation variants, e.g.
+/// "hey there ana"
+///
+/// The variant is picked from `seed` if given and from the name otherwise,
+/// and the pick is the same as in Rust and in the browser.
+///
+/// Raises ValueError for an unknown locale.
+#[pyfunction]
+#[pyo3(signature = (name, *, seed=None, locale=None))]
+fn hello_varied(name: &str, seed: Option<u64>, locale: Option<LocaleArg>) -> PyResult<String> {
+    let locale = locale_or_default(locale)?;
+    let greeter = match seed {
+        Some(seed) => VariedGreeter::with_seed(seed),
+        None => VariedGreeter::new(),
+    };
+    Ok(greeter.greet(&locale, name))
+}
+
+/// Greet a person at a formality level: "intimate", "casual", "neutral",
+/// "formal" or "ceremonial"
+///
+/// The formality picks the salutation, how the parsed name is addressed
+/// ("Chidi" or "Dr. Okafor"), and whether how_are_you=True asks with the
+/// familiar or the polite "you" (du/Sie, tu/vous). `format` is one of
+/// "plain", "html", "markdown", "ansi" or "json".
+///
+/// Raises ValueError for an unknown formality, format or locale.
+#[pyfunction]
+#[pyo3(signature = (
+    name,
+    formality="neutral",
+    *,
+    locale=None,
+    how_are_you=false,
+    format="plain",
+))]
+fn hello_with_formality(
+    name: &str,
+    formality: &str,
+    locale: Option<LocaleArg>,
+    how_are_you: bool,
+    format: &str,
+) -> PyResult<String> {
+    let locale = locale_or_default(locale)?;
+    let formality: Formality = formality
+        .parse()
+        .map_err(|e: hello_rs::FormalityError| PyValueError::new_err(e.to_string()))?;
+    let format: Format = format
+        .parse()
+        .map_err(|e: hello_rs::FormatError| PyValueError::new_err(e.to_string()))?;
+    let person = PersonName::parse(name, &locale);
+    let greeting = hello_rs::Greeting::for_person(person)
+        .formality(formality)
+        .locale(locale)
+        .how_are_you(how_are_you)
+        .build();
+    Ok(format.render(&greeting))
+}
+
+/// Greet someone as described by a config dict, e.g.
+/// {"locale": "de", "formality": "formal", "punctuation": "!"}
+///
+/// The dict follows the same schema hello-rs uses for JSON and TOML
+/// configs; every key is optional.
+///
+/// Raises ValueError if the config doesn't match the schema or holds values
+/// that aren't JSON, such as bytes.
+#[pyfunction]
+fn hello_with_config(py: Python<'_>, name: &str, config: &Bound<'_, PyDict>) -> PyResult<String> {
+    let json: String = py
+        .import("json")?
+        .call_method1("dumps", (config,))
+        .map_err(|e| {
+            let error = PyValueError::new_err(e.value(py).to_string());
+            error.set_cause(py, Some(e));
+            error
+        })?
+        .extract()?;
+    let config: GreetingConfig =
+        serde_json::from_str(&json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(config.greet(name))
+}
+
+/// Make `template` available to greet() as `name`, replacing any style
+/// already registered under that name
+#[pyfunction]
+fn register_style(name: &str, template: &Template) {
+    let mut styles = STYLES.write().unwrap_or_else(|e| e.into_inner());
+    styles.register(name, template.inner.clone());
+}
+
+/// The names of the styles greet() accepts
+#[pyfunction]
+fn styles() -> Vec<String> {
+    let styles = STYLES.read().unwrap_or_else(|e| e.into_inner());
+    styles.names().map(str::to_string).collect()
+}
+
+/// The locale tags that hello-rs ships translations for
+#[pyfunction]
+fn supported_locales() -> Vec<String> {
+    hello_rs::supported_locales()
+        .map(|locale| locale.to_string())
+        .collect()
+}
+
+/// Title-case text the way hello-rs does, e.g. "o'neill" -> "O'Neill"
+///
+/// Name particles such as "van" and "der" stay lowercase, and the casing
+/// rules of `locale` (e.g. Turkish dotted i) are applied when given.
+#[pyfunction]
+#[pyo3(signature = (text, locale=None))]
+fn title_case(text: &str, locale: Option<LocaleArg>) -> PyResult<String> {
+    let title_case = match locale {
+        Some(locale) => TitleCase::for_locale(&locale.parse()?),
+        None => TitleCase::new(),
+    };
+    Ok(title_case.apply(text))
+}
+
+/// A compiled greeting template such as "{salutation}, {name|title}{punct}"
+///
+/// Create one with Template.compile(); it can then render any number of
+/// greetings without being parsed again.
+#[pyclass(frozen, module = "hello_py._rust")]
+struct Template {
+    inner: hello_rs::Template,
+}
+
+#[pymethods]
+impl Template {
+    /// Compile `source`, raising TemplateError if it is malformed
+    ///
+    /// The error message gives the byte offset of the problem.
+    #[staticmethod]
+    fn compile(source: &str) -> PyResult<Self> {
+        hello_rs::Template::compile(source)
+            .map(|inner| Template { inner })
+            .map_err(|e| TemplateError::new_err(e.to_string()))
+    }
+
+    /// Render a greeting for `name`
+    ///
+    /// `salutation` replaces the one from `locale`'s catalog, and
+    /// `punctuation` fills the {punct} placeholder.
+    #[pyo3(signature = (name, *, locale=None, salutation=None, punctuation=""))]
+    fn render(
+        &self,
+        name: &str,
+        locale: Option<LocaleArg>,
+        salutation: Option<&str>,
+        punctuation: &str,
+    ) -> PyResult<String> {
+        let locale = locale_or_default(locale)?;
+        let mut builder = hello_rs::Greeting::builder(name)
+            .locale(locale)
+            .punctuation(punctuation);
+        if let Some(salutation) = salutation {
+            builder = builder.salutation(salutation);
+        }
+        Ok(self.inner.render(&builder.build()))
+    }
+
+    /// The source the template was compiled from
+    #[getter]
+    fn source(&self) -> &str {
+        self.inner.as_str()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Template.compile({:?})", self.inner.as_str())
+    }
+}
+
+/// A normalized language tag such as "pt-BR"
+///
+/// Raises ValueError if `tag` is not a valid language tag. Anything that
+/// takes a locale accepts either a Locale or a tag.
+#[pyclass(frozen, eq, hash, module = "hello_py._rust")]
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Locale {
+    inner: hello_rs::Locale,
+}
+
+#[pymethods]
+impl Locale {
+    #[new]
+    fn new(tag: &str) -> PyResult<Self> {
+        parse_locale(tag).map(|inner| Locale { inner })
+    }
+
+    /// The normalized tag, e.g. "pt-BR"
+    #[getter]
+    fn tag(&self) -> &str {
+        self.inner.as_str()
+    }
+
+    /// The language subtag, e.g. "pt"
+    #[getter]
+    fn language(&self) -> &str {
+        self.inner.language()
+    }
+
+    /// The region subtag, e.g. "BR", if there is one
+    #[getter]
+    fn region(&self) -> Option<&str> {
+        self.inner.region()
+    }
+
+    /// Whether hello-rs ships translations for exactly this tag
+    #[getter]
+    fn is_supported(&self) -> bool {
+        self.inner.is_supported()
+    }
+
+    fn __str__(&self) -> &str {
+        self.inner.as_str()
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!("Locale({})", py_repr(py, self.inner.as_str())?))
+    }
+
+    fn __getnewargs__(&self) -> (&str,) {
+        (self.inner.as_str(),)
+    }
+}
+
+/// A locale argument, given either as a Locale or as a tag
+#[derive(FromPyObject)]
+enum LocaleArg {
+    Locale(Locale),
+    Tag(String),
+}
+
+impl LocaleArg {
+    fn parse(self) -> PyResult<hello_rs::Locale> {
+        match self {
+            LocaleArg::Locale(locale) => Ok(locale.inner),
+            LocaleArg::Tag(tag) => parse_locale(&tag),
+        }
+    }
+}
+
+/// How someone likes to be greeted
+///
+/// Every argument is keyword-only and optional, and they follow the schema
+/// hello_with_config() takes: locale, formality ("intimate" to
+/// "ceremonial"), capitalization ("as_is", "lower", "upper", "sentence" or
+/// "title"), punctuation after the name, gender ("feminine", "masculine" or
+/// "neutral", which welcome agrees with), welcome to welcome the person
+/// instead of greeting them, how_are_you, template, and the format
+/// ("plain", "html", "markdown", "ansi" or "json") used when there is no
+/// template.
+/// isolate_bidi and strip_bidi_controls work as in greet().
+///
+/// Raises ValueError for an unknown value and TemplateError for a malformed
+/// template.
+#[pyclass(frozen, eq, hash, module = "hello_py._rust")]
+#[derive(Clone, PartialEq, Eq)]
+struct GreetingOptions {
+    config: GreetingConfig,
+}
+
+#[pymethods]
+impl GreetingOptions {
+    #[new]
+    #[pyo3(signature = (
+        *,
+        locale=None,
+        formality="neutral",
+        capitalization="as_is",
+        punctuation="",
+        gender="neutral",
+        welcome=false,
+        how_are_you=false,
+        isolate_bidi=false,
+        strip_bidi_controls=false,
+        template=None,
+        format="plain",
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        locale: Option<LocaleArg>,
+        formality: &str,
+        capitalization: &str,
+        punctuation: &str,
+        gender: &str,
+        welcome: bool,
+        how_are_you: bool,
+        isolate_bidi: bool,
+        strip_bidi_controls: bool,
+        template: Option<&str>,
+        format: &str,
+    ) -> PyResult<Self> {
+        let config = GreetingConfig {
+            locale: locale_or_default(locale)?,
+            formality: formality
+                .parse()
+                .map_err(|e: hello_rs::FormalityError| PyValueError::new_err(e.to_string()))?,
+            capitalization: capitalization
+                .parse()
+                .map_err(|e: hello_rs::CapitalizationError| PyValueError::new_err(e.to_string()))?,
+            punctuation: punctuation.into(),
+            gender: gender
+                .parse()
+                .map_err(|e: hello_rs::GenderError| PyValueError::new_err(e.to_string()))?,
+            welcome,
+            how_are_you,
+            bidi: (isolate_bidi || strip_bidi_controls).then(|| {
+                BidiIsolation::new()
+                    .isolate(isolate_bidi)
+                    .strip_controls(strip_bidi_controls)
+            }),
+            template: template
+                .map(hello_rs::Template::compile)
+                .transpose()
+                .map_err(|e| TemplateError::new_err(e.to_string()))?,
+            format: format
+                .parse()
+                .map_err(|e: hello_rs::FormatError| PyValueError::new_err(e.to_string()))?,
+        };
+        Ok(GreetingOptions { config })
+    }
+
+    #[getter]
+    fn locale(&self) -> Locale {
+        Locale {
+            inner: self.config.locale.clone(),
+        }
+    }
+
+    #[getter]
+    fn formality(&self) -> &'static str {
+        self.config.formality.as_str()
+    }
+
+    #[getter]
+    fn capitalization(&self) -> &'static str {
+        self.config.capitalization.as_str()
+    }
+
+    #[getter]
+    fn punctuation(&self) -> &str {
+        &self.config.punctuation
+    }
+
+    #[getter]
+    fn gender(&self) -> &'static str {
+        self.config.gender.as_str()
+    }
+
+    #[getter]
+    fn welcome(&self) -> bool {
+        self.config.welcome
+    }
+
+    #[getter]
+    fn how_are_you(&self) -> bool {
+        self.config.how_are_you
+    }
+
+    #[getter]
+    fn isolate_bidi(&self) -> bool {
+        self.config.bidi.is_some_and(|bidi| bidi.is_isolating())
+    }
+
+    #[getter]
+    fn strip_bidi_controls(&self) -> bool {
+        self.config
+            .bidi
+            .is_some_and(|bidi| bidi.is_stripping_controls())
+    }
+
+    #[getter]
+    fn template(&self) -> Option<&str> {
+        self.config
+            .template
+            .as_ref()
+            .map(hello_rs::Template::as_str)
+    }
+
+    #[getter]
+    fn format(&self) -> &'static str {
+        self.config.format.as_str()
+    }
+
+    /// The options that differ from the defaults, e.g.
+    /// GreetingOptions(locale='de', formality='formal')
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let defaults = GreetingOptions {
+            config: GreetingConfig::default(),
+        }
+        .kwargs(py)?;
+        let mut args = Vec::new();
+        for (key, value) in self.kwargs(py)? {
+            if !defaults
+                .get_item(&key)?
+                .is_some_and(|d| d.eq(&value).unwrap_or(false))
+            {
+                args.push(format!("{key}={}", value.repr()?));
+            }
+        }
+        Ok(format!("GreetingOptions({})", args.join(", ")))
+    }
+
+    fn __getnewargs_ex__<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<(Bound<'py, PyTuple>, Bound<'py, PyDict>)> {
+        Ok((PyTuple::empty(py), self.kwargs(py)?))
+    }
+}
+
+impl GreetingOptions {
+    /// The keyword arguments that would create these options
+    fn kwargs<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("locale", self.config.locale.as_str())?;
+        kwargs.set_item("formality", self.formality())?;
+        kwargs.set_item("capitalization", self.capitalization())?;
+        kwargs.set_item("punctuation", self.punctuation())?;
+        kwargs.set_item("gender", self.gender())?;
+        kwargs.set_item("welcome", self.welcome())?;
+        kwargs.set_item("how_are_you", self.how_are_you())?;
+        kwargs.set_item("isolate_bidi", self.isolate_bidi())?;
+        kwargs.set_item("strip_bidi_controls", self.strip_bidi_controls())?;
+        kwargs.set_item("template", self.template())?;
+        kwargs.set_item("format", self.format())?;
+        Ok(kwargs)
+    }
+}
+
+impl Hash for GreetingOptions {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let GreetingConfig {
+            locale,
+            formality,
+            capitalization,
+            punctuation,
+            gender,
+            welcome,
+            how_are_you,
+            bidi,
+            template,
+            format,
+        } = &self.config;
+        locale.hash(state);
+        formality.hash(state);
+        capitalization.hash(state);
+        punctuation.hash(state);
+        gender.hash(state);
+        welcome.hash(state);
+        how_are_you.hash(state);
+        bidi.hash(state);
+        template
+            .as_ref()
+            .map(hello_rs::Template::as_str)
+            .hash(state);
+        format.hash(state);
+    }
+}
+
+/// A greeting for one person, greeted as `options` describe
+///
+/// The name is parsed into honorific, given and family names, so
+/// `recipient` is e.g. "Dr. Okafor" when the greeting is formal. str() gives
+/// the greeting as the options render it.
+#[pyclass(frozen, eq, hash, module = "hello_py._rust")]
+struct Greeting {
+    name: String,
+    options: GreetingOptions,
+    greeting: hello_rs::Greeting,
+}
+
+#[pymethods]
+impl Greeting {
+    #[new]
+    #[pyo3(signature = (name, options=None))]
+    fn new(name: String, options: Option<GreetingOptions>) -> Self {
+        let options = options.unwrap_or_else(|| GreetingOptions {
+            config: GreetingConfig::default(),
+        });
+        let greeting = options.config.greeting(&name);
+        Greeting {
+            name,
+            options,
+            greeting,
+        }
+    }
+
+    /// The name as given
+    #[getter]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[getter]
+    fn options(&self) -> GreetingOptions {
+        self.options.clone()
+    }
+
+    /// The salutation, e.g. "good day"
+    #[getter]
+    fn salutation(&self) -> &str {
+        self.greeting.salutation()
+    }
+
+    /// How the person is addressed, e.g. "Dr. Okafor"
+    #[getter]
+    fn recipient(&self) -> &str {
+        self.greeting.recipient()
+    }
+
+    #[getter]
+    fn locale(&self) -> Locale {
+        Locale {
+            inner: self.greeting.locale().clone(),
+        }
+    }
+
+    #[getter]
+    fn formality(&self) -> &'static str {
+        self.greeting.formality().as_str()
+    }
+
+    /// The greeting rendered in `format`: "plain", "html", "markdown",
+    /// "ansi" or "json"
+    ///
+    /// Raises ValueError for an unknown format.
+    #[pyo3(signature = (format="plain"))]
+    fn render(&self, format: &str) -> PyResult<String> {
+        let format: Format = format
+            .parse()
+            .map_err(|e: hello_rs::FormatError| PyValueError::new_err(e.to_string()))?;
+        Ok(format.render(&self.greeting))
+    }
+
+    fn __str__(&self) -> String {
+        self.options.config.greet(&self.name)
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let name = py_repr(py, &self.name)?;
+        if self.options.config == GreetingConfig::default() {
+            Ok(format!("Greeting({name})"))
+        } else {
+            Ok(format!("Greeting({name}, {})", self.options.__repr__(py)?))
+        }
+    }
+
+    fn __getnewargs__(&self) -> (&str, GreetingOptions) {
+        (&self.name, self.options.clone())
+    }
+}
+
+impl PartialEq for Greeting {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.options == other.options
+    }
+}
+
+impl Hash for Greeting {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.options.hash(state);
+    }
+}
+
+fn parse_locale(locale: &str) -> PyResult<hello_rs::Locale> {
+    hello_rs::Locale::parse(locale).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Parse an optional locale argument, defaulting to English
+fn locale_or_default(locale: Option<LocaleArg>) -> PyResult<hello_rs::Locale> {
+    Ok(locale
+        .map(LocaleArg::parse)
+        .transpose()?
+        .unwrap_or_default())
+}
+
+/// `s` quoted the way Python's repr() quotes a str
+fn py_repr(py: Python<'_>, s: &str) -> PyResult<String> {
+    Ok(PyString::new(py, s).repr()?.to_string())
 }
 
 /// The Python module that exposes the Rust functions
-#[pymodule]
+///
+/// It keeps no state that relies on the GIL, so free-threaded Python 3.13+
+/// can import it without turning the GIL back on.
+#[pymodule(gil_used = false)]
 #[pyo3(name = "_rust")]
 fn hello_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(greet, m)?)?;
     m.add_function(wrap_pyfunction!(hello, m)?)?;
+    m.add_function(wrap_pyfunction!(hello_in, m)?)?;
+    m.add_function(wrap_pyfunction!(hello_many, m)?)?;
+    m.add_function(wrap_pyfunction!(hello_all, m)?)?;
+    m.add_function(wrap_pyfunction!(hello_now, m)?)?;
+    m.add_function(wrap_pyfunction!(hello_varied, m)?)?;
+    m.add_function(wrap_pyfunction!(hello_with_config, m)?)?;
+    m.add_function(wrap_pyfunction!(hello_with_formality, m)?)?;
+    m.add_function(wrap_pyfunction!(register_style, m)?)?;
+    m.add_function(wrap_pyfunction!(styles, m)?)?;
+    m.add_function(wrap_pyfunction!(supported_locales, m)?)?;
+    m.add_function(wrap_pyfunction!(title_case, m)?)?;
+    m.add_function(wrap_pyfunction!(try_hello, m)?)?;
+    m.add_class::<Greeting>()?;
+    m.add_class::<GreetingOptions>()?;
+    m.add_class::<Locale>()?;
+    m.add_class::<Template>()?;
+
+    let py = m.py();
+    m.add("HelloError", py.get_type::<HelloError>())?;
+    m.add("EmptyNameError", py.get_type::<EmptyNameError>())?;
+    m.add("NameTooLongError", py.get_type::<NameTooLongError>())?;
+    m.add(
+        "ControlCharactersError",
+        py.get_type::<ControlCharactersError>(),
+    )?;
+    m.add("UnsafeBidiError", py.get_type::<UnsafeBidiError>())?;
+    m.add(
+        "InvalidScriptMixError",
+        py.get_type::<InvalidScriptMixError>(),
+    )?;
+    m.add("TemplateError", py.get_type::<TemplateError>())?;
     Ok(())
 }
@@ -1,5 +1,57 @@
 """Python library with FFI bindings to hello-rs Rust library."""
 
-from hello_py._rust import hello
+from hello_py._rust import (
+    ControlCharactersError,
+    EmptyNameError,
+    Greeting,
+    GreetingOptions,
+    HelloError,
+    InvalidScriptMixError,
+    Locale,
+    NameTooLongError,
+    Template,
+    TemplateError,
+    UnsafeBidiError,
+    greet,
+    hello,
+    hello_all,
+    hello_in,
+    hello_many,
+    hello_now,
+    hello_varied,
+    hello_with_config,
+    hello_with_formality,
+    register_style,
+    styles,
+    supported_locales,
+    title_case,
+    try_hello,
+)
 
-__all__ = ["hello"]
+__all__ = [
+    "ControlCharactersError",
+    "EmptyNameError",
+    "Greeting",
+    "GreetingOptions",
+    "HelloError",
+    "InvalidScriptMixError",
+    "Locale",
+    "NameTooLongError",
+    "Template",
+    "TemplateError",
+    "UnsafeBidiError",
+    "greet",
+    "hello",
+    "hello_all",
+    "hello_in",
+    "hello_many",
+    "hello_now",
+    "hello_varied",
+    "hello_with_config",
+    "hello_with_formality",
+    "register_style",
+    "styles",
+    "supported_locales",
+    "title_case",
+    "try_hello",
+]
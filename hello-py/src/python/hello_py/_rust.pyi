@@ -0,0 +1,144 @@
+"""Type stubs for the hello_py._rust extension module."""
+
+from collections.abc import Iterable
+from typing import Any, Literal, final
+
+Formality = Literal["intimate", "casual", "neutral", "formal", "ceremonial"]
+Format = Literal["plain", "html", "markdown", "ansi", "json"]
+Capitalization = Literal["as_is", "lower", "upper", "sentence", "title"]
+Gender = Literal["feminine", "masculine", "neutral"]
+
+class HelloError(ValueError): ...
+class EmptyNameError(HelloError): ...
+class NameTooLongError(HelloError): ...
+class ControlCharactersError(HelloError): ...
+class UnsafeBidiError(HelloError): ...
+class InvalidScriptMixError(HelloError): ...
+class TemplateError(ValueError): ...
+
+@final
+class Locale:
+    def __new__(cls, tag: str) -> Locale: ...
+    @property
+    def tag(self) -> str: ...
+    @property
+    def language(self) -> str: ...
+    @property
+    def region(self) -> str | None: ...
+    @property
+    def is_supported(self) -> bool: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+@final
+class GreetingOptions:
+    def __new__(
+        cls,
+        *,
+        locale: Locale | str | None = None,
+        formality: Formality = "neutral",
+        capitalization: Capitalization = "as_is",
+        punctuation: str = "",
+        gender: Gender = "neutral",
+        welcome: bool = False,
+        how_are_you: bool = False,
+        isolate_bidi: bool = False,
+        strip_bidi_controls: bool = False,
+        template: str | None = None,
+        format: Format = "plain",
+    ) -> GreetingOptions: ...
+    @property
+    def locale(self) -> Locale: ...
+    @property
+    def formality(self) -> Formality: ...
+    @property
+    def capitalization(self) -> Capitalization: ...
+    @property
+    def punctuation(self) -> str: ...
+    @property
+    def gender(self) -> Gender: ...
+    @property
+    def welcome(self) -> bool: ...
+    @property
+    def how_are_you(self) -> bool: ...
+    @property
+    def isolate_bidi(self) -> bool: ...
+    @property
+    def strip_bidi_controls(self) -> bool: ...
+    @property
+    def template(self) -> str | None: ...
+    @property
+    def format(self) -> Format: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+@final
+class Greeting:
+    def __new__(cls, name: str, options: GreetingOptions | None = None) -> Greeting: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def options(self) -> GreetingOptions: ...
+    @property
+    def salutation(self) -> str: ...
+    @property
+    def recipient(self) -> str: ...
+    @property
+    def locale(self) -> Locale: ...
+    @property
+    def formality(self) -> Formality: ...
+    def render(self, format: Format = "plain") -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+@final
+class Template:
+    @staticmethod
+    def compile(source: str) -> Template: ...
+    def render(
+        self,
+        name: str,
+        *,
+        locale: Locale | str | None = None,
+        salutation: str | None = None,
+        punctuation: str = "",
+    ) -> str: ...
+    @property
+    def source(self) -> str: ...
+
+def hello(name: str) -> str: ...
+def hello_many(names: Iterable[str], *, threads: int = 1) -> list[str]: ...
+def hello_all(names: list[str], locale: Locale | str | None = None) -> str: ...
+def try_hello(name: str) -> str: ...
+def hello_in(locale: Locale | str, name: str) -> str: ...
+def hello_now(
+    name: str,
+    *,
+    locale: Locale | str | None = None,
+    utc_offset_minutes: int = 0,
+    timestamp: int | None = None,
+) -> str: ...
+def greet(
+    name: str,
+    style: str = "plain",
+    *,
+    locale: Locale | str | None = None,
+    isolate_bidi: bool = False,
+    strip_bidi_controls: bool = False,
+) -> str: ...
+def hello_varied(
+    name: str, *, seed: int | None = None, locale: Locale | str | None = None
+) -> str: ...
+def hello_with_formality(
+    name: str,
+    formality: Formality = "neutral",
+    *,
+    locale: Locale | str | None = None,
+    how_are_you: bool = False,
+    format: Format = "plain",
+) -> str: ...
+def hello_with_config(name: str, config: dict[str, Any]) -> str: ...
+def register_style(name: str, template: Template) -> None: ...
+def styles() -> list[str]: ...
+def supported_locales() -> list[str]: ...
+def title_case(text: str, locale: Locale | str | None = None) -> str: ...
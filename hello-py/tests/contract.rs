@@ -0,0 +1,11 @@
+//! Runs hello-rs's contract against the build of hello-rs this crate links.
+//!
+//! hello-py's `hello()` hands hello-rs's greeting to Python unchanged, so a
+//! hello-rs release that changes the format should fail here, not in users'
+//! code.
+
+#[test]
+fn test_hello_rs_contract() {
+    let mut buf = String::new();
+    hello_rs::contract::verify(|name| hello_rs::hello_into(&mut buf, name).to_owned());
+}
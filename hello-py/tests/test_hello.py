@@ -1,7 +1,44 @@
 """Unit tests for the hello_py library."""
 
+import json
+import os
+import pickle
+import sys
+import sysconfig
+import threading
+from pathlib import Path
+
 import pytest
-from hello_py import hello
+import hello_py
+from hello_py import (
+    Greeting,
+    GreetingOptions,
+    Locale,
+    greet,
+    hello,
+    hello_all,
+    hello_in,
+    hello_many,
+    hello_now,
+    hello_varied,
+    hello_with_config,
+    hello_with_formality,
+    supported_locales,
+    title_case,
+    try_hello,
+)
+
+# hello-rs's golden vectors, replayed here and in hello-wasm's checks so the
+# bindings can't drift apart. Nix points HELLO_RS_VECTORS at the copy in the
+# hello-rs input; otherwise the file is read from the hello-rs checkout next
+# to this one.
+VECTOR_FILE = Path(
+    os.environ.get(
+        "HELLO_RS_VECTORS",
+        Path(__file__).resolve().parents[2] / "hello-rs" / "vectors" / "greetings.json",
+    )
+)
+VECTORS = json.loads(VECTOR_FILE.read_text(encoding="utf-8"))
 
 
 def test_hello_basic():
@@ -13,3 +50,387 @@ def test_hello_empty_string():
     """Test hello with an empty string."""
     result = hello("")
     assert result == "hello "
+
+def test_hello_many():
+    """Test that hello_many() greets every name, in order, like hello()."""
+    names = ["ana", "bo", "", "محمد", "ana\nbo"]
+    assert hello_many(names) == [hello(name) for name in names]
+    assert hello_many([]) == []
+
+
+def test_hello_many_iterables():
+    """Test that hello_many() accepts any iterable of str."""
+    assert hello_many(("ana", "bo")) == ["hello ana", "hello bo"]
+    assert hello_many(name for name in ["ana", "bo"]) == ["hello ana", "hello bo"]
+    assert hello_many({"ana": 1}) == ["hello ana"]
+    with pytest.raises(TypeError):
+        hello_many(["ana", 1])
+    with pytest.raises(TypeError):
+        hello_many(42)
+
+
+@pytest.mark.parametrize("threads", [0, 1, 2, 7])
+def test_hello_many_threads(threads):
+    """Test that splitting a batch across threads keeps the order."""
+    names = [f"name {i}" for i in range(50_000)]
+    assert hello_many(names, threads=threads) == [f"hello name {i}" for i in range(50_000)]
+
+
+def test_hello_many_concurrent():
+    """Test that hello_many() can run from several Python threads at once."""
+    names = [str(i) for i in range(20_000)]
+    expected = [f"hello {i}" for i in range(20_000)]
+    results = []
+
+    def run():
+        results.append(hello_many(names, threads=2))
+
+    workers = [threading.Thread(target=run) for _ in range(4)]
+    for worker in workers:
+        worker.start()
+    for worker in workers:
+        worker.join()
+    assert results == [expected] * 4
+
+
+@pytest.mark.skipif(
+    not sysconfig.get_config_var("Py_GIL_DISABLED"), reason="needs free-threaded Python"
+)
+def test_free_threaded():
+    """Test that importing the extension leaves the GIL disabled."""
+    assert not sys._is_gil_enabled()
+
+
+def test_hello_all():
+    """Test greeting several people at once."""
+    assert hello_all(["Ana", "Bo", "Cy"]) == "hello Ana, Bo, and Cy"
+    assert hello_all(["Ana", "Isabel"], locale="es") == "hola Ana e Isabel"
+
+
+def test_hello_all_collapses_long_lists():
+    """Test that long lists are summarized."""
+    names = [f"n{i}" for i in range(1, 23)]
+    assert hello_all(names).endswith("n10, and 12 others")
+
+
+def test_hello_in():
+    """Test greeting in another locale."""
+    assert hello_in("es", "mundo") == "hola mundo"
+    assert hello_in("pt_BR", "ana") == "olá ana"
+
+
+def test_hello_in_falls_back_to_english():
+    """Test that unknown locales fall back to English."""
+    assert hello_in("xx", "world") == "hello world"
+
+
+def test_hello_in_invalid_locale():
+    """Test that malformed locale tags are rejected."""
+    with pytest.raises(ValueError):
+        hello_in("not a locale", "world")
+
+
+def test_supported_locales():
+    """Test that the catalog's locales are listed."""
+    locales = supported_locales()
+    assert "en" in locales
+    assert "pt-BR" in locales
+
+
+def test_hello_now():
+    """Test greetings for a given time of day."""
+    # 2024-06-03T08:00:00Z
+    morning = 1_717_401_600
+    assert hello_now("Ana", timestamp=morning) == "good morning Ana"
+    sydney = hello_now("Ana", timestamp=morning, utc_offset_minutes=600)
+    assert sydney == "good evening Ana"
+    assert hello_now("Ana", timestamp=morning, locale="es") == "buenos días Ana"
+
+
+def test_hello_now_holiday():
+    """Test that holidays get their own greeting."""
+    # 2024-12-25T10:00:00Z
+    assert hello_now("Ana", timestamp=1_735_120_800) == "merry Christmas Ana"
+
+
+def test_hello_now_extremes():
+    """Test that out-of-range times and offsets don't overflow."""
+    for timestamp in (2**63 - 1, -(2**63)):
+        for offset in (2**31 - 1, 60, -(2**31)):
+            assert hello_now("Ana", timestamp=timestamp, utc_offset_minutes=offset).endswith(" Ana")
+
+
+def test_hello_now_system_clock():
+    """Test that the system clock is used by default."""
+    assert hello_now("Ana").endswith(" Ana")
+
+
+def test_template():
+    """Test rendering a compiled template."""
+    template = hello_py.Template.compile("{salutation|title}, {name|trim|upper}{punct}")
+    assert template.render(" ana ", punctuation="!") == "Hello, ANA!"
+    assert template.render("ana", locale="de") == "Hallo, ANA"
+    assert template.render("ana", salutation="welcome") == "Welcome, ANA"
+    assert template.source == "{salutation|title}, {name|trim|upper}{punct}"
+
+
+def test_template_errors():
+    """Test that malformed templates report where the problem is."""
+    with pytest.raises(hello_py.TemplateError, match="byte 14"):
+        hello_py.Template.compile("{salutation} {nmae}")
+    with pytest.raises(ValueError):
+        hello_py.Template.compile("{name|shout}")
+
+
+def test_greet_styles():
+    """Test greeting in each built-in style."""
+    assert greet("ana") == "hello ana"
+    assert greet("ana", "fancy") == "Hello Ana!"
+    assert greet("okafor", "formal") == "Good Day, Okafor."
+    assert greet("ana", "shout", locale="de") == "HALLO ANA!"
+    assert greet("ana", "varied") == "greetings ana"
+    builtin = {"plain", "fancy", "formal", "shout", "varied", "given", "family", "full"}
+    assert builtin <= set(hello_py.styles())
+
+
+def test_hello_varied():
+    """Test that variants are picked the same way as in hello-rs."""
+    assert hello_varied("ana") == "greetings ana"
+    assert hello_varied("bo") == "hello bo"
+    assert hello_varied("ana", seed=0) == "hi ana"
+    assert hello_varied("ana", seed=42, locale="es") == "saludos ana"
+    assert len({hello_varied("", seed=seed) for seed in range(50)}) == 5
+    with pytest.raises(ValueError):
+        hello_varied("ana", locale="not a tag")
+
+
+def test_hello_with_formality():
+    """Test that the formality picks the salutation, address and "you"."""
+    assert hello_with_formality("Dr. Chidi Okafor") == "hello Chidi Okafor"
+    assert hello_with_formality("Dr. Chidi Okafor", "casual") == "hey Chidi"
+    assert hello_with_formality("Dr. Chidi Okafor", "formal") == "good day, Dr. Okafor"
+    assert (
+        hello_with_formality("Anna Schmidt", "casual", locale="de", how_are_you=True)
+        == "hi Anna, wie geht's dir?"
+    )
+    assert (
+        hello_with_formality("Frau Anna Schmidt", "formal", locale="de", how_are_you=True)
+        == "guten Tag, Frau Schmidt, wie geht es Ihnen?"
+    )
+    with pytest.raises(ValueError, match="unknown formality"):
+        hello_with_formality("ana", "posh")
+
+
+def test_hello_with_formality_format():
+    """Test rendering the greeting as HTML, Markdown, ANSI and JSON."""
+    assert (
+        hello_with_formality("Dr. Chidi O'Kafor", "formal", format="html")
+        == '<span lang="en">good day, <bdi>Dr. O&#39;Kafor</bdi></span>'
+    )
+    assert hello_with_formality("Chidi_Okafor", format="markdown") == r"hello **Chidi\_Okafor**"
+    assert hello_with_formality("Chidi", format="ansi") == "hello \x1b[1mChidi\x1b[22m"
+    greeting = json.loads(hello_with_formality("Chidi", "casual", format="json"))
+    assert greeting["text"] == "hey Chidi"
+    assert greeting["formality"] == "casual"
+    with pytest.raises(ValueError, match="unknown format"):
+        hello_with_formality("ana", format="yaml")
+
+
+def test_greet_bidi():
+    """Test that right-to-left names can be isolated and stripped of overrides."""
+    assert greet("محمد", "fancy") == "Hello محمد!"
+    assert greet("محمد", "fancy", isolate_bidi=True) == "Hello \u2068محمد\u2069!"
+    assert greet("Ana", "fancy", isolate_bidi=True) == "Hello Ana!"
+    assert greet("a\u202eb", strip_bidi_controls=True) == "hello ab"
+
+
+def test_hello_with_config():
+    """Test greeting with a config dict in the hello-rs config schema."""
+    assert hello_with_config("ana", {}) == "hello ana"
+    config = {
+        "locale": "de",
+        "formality": "formal",
+        "capitalization": "sentence",
+        "punctuation": "!",
+    }
+    assert hello_with_config("Frau Anna Schmidt", config) == "Guten Tag, Frau Schmidt!"
+    config = {"template": "{salutation|upper} {name}", "format": "html"}
+    assert hello_with_config("ana", config) == "HELLO ana"
+    with pytest.raises(ValueError, match="unknown field"):
+        hello_with_config("ana", {"formalty": "formal"})
+    with pytest.raises(ValueError, match="invalid locale tag"):
+        hello_with_config("ana", {"locale": "not a tag"})
+    with pytest.raises(ValueError, match="not JSON serializable"):
+        hello_with_config("ana", {"punctuation": b"!"})
+
+
+def test_register_style():
+    """Test that a template can be registered as a new style."""
+    hello_py.register_style("bracketed", hello_py.Template.compile("[{salutation}] {name}"))
+    assert greet("bo", "bracketed") == "[hello] bo"
+    assert "bracketed" in hello_py.styles()
+    with pytest.raises(ValueError, match="whisper"):
+        greet("bo", "whisper")
+
+
+def test_title_case():
+    """Test that names are title-cased by hello-rs's rules."""
+    assert title_case("hello o'neill") == "Hello O'Neill"
+    assert title_case("hello van der berg") == "Hello van der Berg"
+    assert title_case("hello anne-marie") == "Hello Anne-Marie"
+
+
+def test_title_case_locale():
+    """Test locale-specific title-casing."""
+    assert title_case("merhaba ismail", locale="tr") == "Merhaba İsmail"
+    assert title_case("hallo ijsbrand", locale="nl") == "Hallo IJsbrand"
+
+
+def test_try_hello():
+    """Test that valid names are greeted."""
+    assert try_hello("world") == "hello world"
+
+
+@pytest.mark.parametrize(
+    ("name", "error"),
+    [
+        ("", hello_py.EmptyNameError),
+        ("a" * 1000, hello_py.NameTooLongError),
+        ("ana\nbo", hello_py.ControlCharactersError),
+        ("ana\u202etxt.exe", hello_py.UnsafeBidiError),
+        ("p\u0430ypal", hello_py.InvalidScriptMixError),
+    ],
+)
+def test_try_hello_rejects(name, error):
+    """Test that each kind of invalid name raises its own exception."""
+    with pytest.raises(error):
+        try_hello(name)
+    with pytest.raises(hello_py.HelloError):
+        try_hello(name)
+    with pytest.raises(ValueError):
+        try_hello(name)
+
+
+def test_locale():
+    """Test that a Locale normalizes its tag and exposes its subtags."""
+    locale = Locale("pt_br")
+    assert locale.tag == "pt-BR"
+    assert str(locale) == "pt-BR"
+    assert locale.language == "pt"
+    assert locale.region == "BR"
+    assert locale.is_supported
+    assert Locale("de").region is None
+    assert hello_in(locale, "ana") == hello_in("pt-BR", "ana")
+    with pytest.raises(ValueError):
+        Locale("not a tag")
+
+
+def test_greeting_options():
+    """Test that GreetingOptions takes keyword arguments and reads them back."""
+    options = GreetingOptions(locale="de", formality="formal", punctuation="!", isolate_bidi=True)
+    assert options.locale == Locale("de")
+    assert options.formality == "formal"
+    assert options.capitalization == "as_is"
+    assert options.punctuation == "!"
+    assert options.isolate_bidi
+    assert not options.strip_bidi_controls
+    assert options.template is None
+    assert options.format == "plain"
+    with pytest.raises(TypeError):
+        GreetingOptions("de")
+    with pytest.raises(ValueError):
+        GreetingOptions(formality="stiff")
+    with pytest.raises(ValueError, match="unknown capitalization"):
+        GreetingOptions(capitalization="shouty")
+    with pytest.raises(ValueError, match="unknown gender"):
+        GreetingOptions(gender="other")
+    with pytest.raises(ValueError, match="unknown format"):
+        GreetingOptions(format="pdf")
+    stripped = GreetingOptions(capitalization="title", gender="feminine", strip_bidi_controls=True)
+    assert stripped.capitalization == "title"
+    assert stripped.gender == "feminine"
+    assert not stripped.isolate_bidi
+    assert stripped.strip_bidi_controls
+    assert hash(stripped) != hash(GreetingOptions(capitalization="title", gender="feminine"))
+
+
+def test_greeting_options_welcome():
+    """Test that gender takes effect when welcoming someone."""
+    feminine = GreetingOptions(locale="es", gender="feminine", welcome=True)
+    assert feminine.welcome
+    assert str(Greeting("ana", feminine)) == "bienvenida ana"
+    masculine = GreetingOptions(locale="es", gender="masculine", welcome=True)
+    assert str(Greeting("ana", masculine)) == "bienvenido ana"
+    assert repr(feminine) == "GreetingOptions(locale='es', gender='feminine', welcome=True)"
+    with pytest.raises(hello_py.TemplateError):
+        GreetingOptions(template="{salutation")
+
+
+def test_greeting():
+    """Test that a Greeting exposes its parts and renders as its options say."""
+    options = GreetingOptions(locale="pt-BR", formality="formal")
+    greeting = Greeting("Dr. Chidi Okafor", options)
+    assert greeting.name == "Dr. Chidi Okafor"
+    assert greeting.options == options
+    assert greeting.salutation == "cumprimentos"
+    assert greeting.recipient == "Dr. Okafor"
+    assert greeting.locale == Locale("pt-BR")
+    assert greeting.formality == "formal"
+    assert str(greeting) == "cumprimentos, Dr. Okafor"
+    assert greeting.render("html") == '<span lang="pt-BR">cumprimentos, <bdi>Dr. Okafor</bdi></span>'
+    assert str(Greeting("ana")) == hello_with_config("ana", {})
+    with pytest.raises(ValueError):
+        greeting.render("pdf")
+
+
+def test_greeting_matches_config():
+    """Test that GreetingOptions greets the same as the equivalent config dict."""
+    options = GreetingOptions(locale="de", formality="formal", punctuation="!")
+    config = {"locale": "de", "formality": "formal", "punctuation": "!"}
+    assert str(Greeting("ana", options)) == hello_with_config("ana", config)
+
+
+@pytest.mark.parametrize(
+    "value",
+    [
+        Locale("pt-BR"),
+        GreetingOptions(),
+        GreetingOptions(locale="tr", how_are_you=True, strip_bidi_controls=True),
+        GreetingOptions(template="{salutation|title}, {name}{punct}", punctuation="!"),
+        Greeting("ana"),
+        Greeting("Dr. Chidi Okafor", GreetingOptions(formality="formal", format="json")),
+    ],
+    ids=repr,
+)
+def test_value_semantics(value):
+    """Test that repr() round-trips and equal values hash and pickle alike."""
+    namespace = {"Greeting": Greeting, "GreetingOptions": GreetingOptions, "Locale": Locale}
+    copy = eval(repr(value), namespace)
+    assert copy == value
+    assert hash(copy) == hash(value)
+    assert pickle.loads(pickle.dumps(value)) == value
+    assert len({value, copy}) == 1
+
+
+def test_value_inequality():
+    """Test that values differing in any field compare unequal."""
+    assert Locale("pt-BR") != Locale("pt")
+    assert GreetingOptions(formality="formal") != GreetingOptions()
+    assert Greeting("ana") != Greeting("bo")
+    assert Greeting("ana") != Greeting("ana", GreetingOptions(locale="de"))
+    assert Locale("de") != "de"
+
+
+def test_golden_vector_version():
+    """Test that the vector file is in the format these tests understand."""
+    assert VECTORS["version"] == 1
+
+
+@pytest.mark.parametrize(
+    "vector",
+    VECTORS["vectors"],
+    ids=lambda v: f"{v['style']}-{v['locale']}-{v['name']}",
+)
+def test_golden_vector(vector):
+    """Test that greet() matches hello-rs on each shared golden vector."""
+    assert greet(vector["name"], vector["style"], locale=vector["locale"]) == vector["expected"]
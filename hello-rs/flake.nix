@@ -37,11 +37,14 @@
           cargo-test = pkgs.stdenv.mkDerivation {
             name = "hello-rs-cargo-test";
             src = ./.;
-            nativeBuildInputs = with pkgs; [ cargo rustc ];
+            cargoDeps = pkgs.rustPlatform.importCargoLock {
+              lockFile = ./Cargo.lock;
+            };
+            nativeBuildInputs = with pkgs; [ cargo rustc rustPlatform.cargoSetupHook ];
 
             buildPhase = ''
-              # Run cargo test and capture output
-              cargo test --no-fail-fast 2>&1 | tee test-output.txt
+              # Run cargo test, including the optional serde support, and capture output
+              cargo test --no-fail-fast --all-features 2>&1 | tee test-output.txt
             '';
 
             installPhase = ''
@@ -95,6 +98,31 @@ EOF
 
             doCheck = false;  # We handle testing in buildPhase
           };
+
+          # Build the library without the default `std` feature, as firmware
+          # and bare wasm32-unknown-unknown users do, and run tests/no_std.rs
+          no-std = pkgs.stdenv.mkDerivation {
+            name = "hello-rs-no-std";
+            src = ./.;
+            cargoDeps = pkgs.rustPlatform.importCargoLock {
+              lockFile = ./Cargo.lock;
+            };
+            nativeBuildInputs = with pkgs; [ cargo rustc rustPlatform.cargoSetupHook ];
+
+            buildPhase = ''
+              cargo build --lib --no-default-features
+              cargo build --lib --no-default-features --features serde,contract
+              cargo test --no-default-features --test no_std 2>&1 | tee test-output.txt
+            '';
+
+            installPhase = ''
+              mkdir -p $out
+              cp test-output.txt $out/full-output.txt
+              grep -q "test result: ok" test-output.txt
+            '';
+
+            doCheck = false;
+          };
         };
 
         devShells.default = pkgs.mkShell {
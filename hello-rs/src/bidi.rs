@@ -0,0 +1,215 @@
+//! Keeping right-to-left names from scrambling the greeting around them.
+//!
+//! A name such as "محمد" placed in a left-to-right sentence can drag
+//! neighbouring punctuation into its own run, so "Hello محمد!" displays with
+//! the "!" on the wrong side. Wrapping the name in a first-strong isolate
+//! (U+2068 ... U+2069) keeps its direction to itself.
+
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::unicode::in_ranges;
+
+/// FIRST STRONG ISOLATE
+pub(crate) const FSI: char = '\u{2068}';
+/// POP DIRECTIONAL ISOLATE
+pub(crate) const PDI: char = '\u{2069}';
+
+/// Bidi embeddings, overrides and isolates
+const BIDI_CONTROLS: &[(char, char)] = &[('\u{202A}', '\u{202E}'), ('\u{2066}', '\u{2069}')];
+
+/// Blocks of strong right-to-left characters: Hebrew, Arabic, Syriac,
+/// Thaana, N'Ko and their presentation forms, plus the RIGHT-TO-LEFT MARK
+const RIGHT_TO_LEFT: &[(char, char)] = &[
+    ('\u{0590}', '\u{08FF}'),
+    ('\u{200F}', '\u{200F}'),
+    ('\u{FB1D}', '\u{FDFF}'),
+    ('\u{FE70}', '\u{FEFE}'),
+    ('\u{10800}', '\u{10FFF}'),
+    ('\u{1E800}', '\u{1EFFF}'),
+];
+
+/// Whether `c` is a bidi embedding, override or isolate
+pub(crate) fn is_bidi_control(c: char) -> bool {
+    in_ranges(c, BIDI_CONTROLS)
+}
+
+fn is_right_to_left(c: char) -> bool {
+    in_ranges(c, RIGHT_TO_LEFT)
+}
+
+/// The direction a run of text is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// e.g. Latin, Cyrillic, Han
+    LeftToRight,
+    /// e.g. Hebrew, Arabic
+    RightToLeft,
+}
+
+impl Direction {
+    /// The direction of the first strongly directional character in `text`,
+    /// or `None` if it has none, e.g. only digits and punctuation
+    ///
+    /// ```
+    /// use hello_rs::Direction;
+    ///
+    /// assert_eq!(Direction::of("שרה"), Some(Direction::RightToLeft));
+    /// assert_eq!(Direction::of("42 Ana"), Some(Direction::LeftToRight));
+    /// assert_eq!(Direction::of("42"), None);
+    /// ```
+    pub fn of(text: &str) -> Option<Direction> {
+        text.chars().find_map(|c| {
+            if is_right_to_left(c) {
+                Some(Direction::RightToLeft)
+            } else if c.is_alphabetic() {
+                Some(Direction::LeftToRight)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// How names are made safe to embed in a greeting.
+///
+/// Names containing right-to-left text, or bidi controls that could leak
+/// into the rest of the line, are wrapped in FSI/PDI isolates. Names that
+/// are entirely left-to-right can't be reordered and pass through unchanged.
+///
+/// ```
+/// use hello_rs::BidiIsolation;
+///
+/// let bidi = BidiIsolation::new();
+/// assert_eq!(bidi.apply("Ana"), "Ana");
+/// assert_eq!(bidi.apply("שרה"), "\u{2068}שרה\u{2069}");
+///
+/// let stripped = bidi.strip_controls(true);
+/// assert_eq!(stripped.apply("Ana\u{202E}naB"), "AnanaB");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct BidiIsolation {
+    #[cfg_attr(feature = "uniffi", uniffi(default = true))]
+    isolate: bool,
+    #[cfg_attr(feature = "uniffi", uniffi(default = false))]
+    strip_controls: bool,
+}
+
+impl BidiIsolation {
+    /// Isolate names that need it and keep any bidi controls inside them
+    pub const fn new() -> Self {
+        BidiIsolation {
+            isolate: true,
+            strip_controls: false,
+        }
+    }
+
+    /// Wrap names that need it in FSI/PDI isolates, on by default
+    pub fn isolate(mut self, isolate: bool) -> Self {
+        self.isolate = isolate;
+        self
+    }
+
+    /// Remove bidi embeddings, overrides and isolates from names
+    pub fn strip_controls(mut self, strip: bool) -> Self {
+        self.strip_controls = strip;
+        self
+    }
+
+    /// Whether names that need it are wrapped in isolates
+    pub fn is_isolating(&self) -> bool {
+        self.isolate
+    }
+
+    /// Whether bidi controls are removed from names
+    pub fn is_stripping_controls(&self) -> bool {
+        self.strip_controls
+    }
+
+    /// Whether `name` would be wrapped in isolates
+    pub fn isolates(&self, name: &str) -> bool {
+        self.isolate
+            && name
+                .chars()
+                .any(|c| is_right_to_left(c) || (!self.strip_controls && is_bidi_control(c)))
+    }
+
+    /// Write `name` into `out`, isolated and stripped as configured
+    pub fn write<W: Write + ?Sized>(&self, name: &str, out: &mut W) -> fmt::Result {
+        let isolate = self.isolates(name);
+        if isolate {
+            out.write_char(FSI)?;
+        }
+        if self.strip_controls {
+            name.split(is_bidi_control)
+                .try_for_each(|piece| out.write_str(piece))?;
+        } else {
+            out.write_str(name)?;
+        }
+        if isolate {
+            out.write_char(PDI)?;
+        }
+        Ok(())
+    }
+
+    /// `name`, isolated and stripped as configured
+    pub fn apply(&self, name: &str) -> String {
+        let mut out = String::with_capacity(name.len());
+        self.write(name, &mut out)
+            .expect("writing to a String cannot fail");
+        out
+    }
+}
+
+impl Default for BidiIsolation {
+    fn default() -> Self {
+        BidiIsolation::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Direction::of("محمد"), Some(Direction::RightToLeft));
+        assert_eq!(Direction::of("Ana שרה"), Some(Direction::LeftToRight));
+        assert_eq!(Direction::of("(שרה)"), Some(Direction::RightToLeft));
+        assert_eq!(Direction::of(""), None);
+    }
+
+    #[test]
+    fn test_isolation() {
+        let bidi = BidiIsolation::new();
+        assert_eq!(bidi.apply("Ana"), "Ana");
+        assert_eq!(bidi.apply("Ana שרה"), "\u{2068}Ana שרה\u{2069}");
+        // An unterminated override stays inside the isolate
+        assert_eq!(bidi.apply("a\u{202E}b"), "\u{2068}a\u{202E}b\u{2069}");
+        assert!(!bidi.isolate(false).isolates("שרה"));
+        assert!(bidi.is_isolating());
+        assert!(!bidi.isolate(false).is_isolating());
+    }
+
+    #[test]
+    fn test_strip_controls() {
+        let bidi = BidiIsolation::new().strip_controls(true);
+        assert!(bidi.is_stripping_controls());
+        assert!(!BidiIsolation::new().is_stripping_controls());
+        assert_eq!(bidi.apply("a\u{202E}b\u{202C}"), "ab");
+        assert_eq!(bidi.apply("\u{2067}שרה"), "\u{2068}שרה\u{2069}");
+        assert_eq!(
+            BidiIsolation::new()
+                .isolate(false)
+                .strip_controls(true)
+                .apply("\u{2067}שרה\u{2069}"),
+            "שרה"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Choosing a salutation from the time of day and the calendar.
+//!
+//! Holidays are computed offline: fixed-date holidays are matched directly
+//! and Easter is found with the Gregorian computus.
+
+use crate::{catalog, CivilDate, Locale, ZonedTime};
+
+/// Part of the day, as far as greetings are concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DayPeriod {
+    /// 05:00 until noon
+    Morning,
+    /// Noon until 18:00
+    Afternoon,
+    /// 18:00 until 05:00
+    Evening,
+}
+
+impl DayPeriod {
+    /// The period containing local `hour` (0 through 23)
+    pub fn of_hour(hour: u8) -> Self {
+        match hour {
+            5..=11 => DayPeriod::Morning,
+            12..=17 => DayPeriod::Afternoon,
+            _ => DayPeriod::Evening,
+        }
+    }
+
+    fn message_key(self) -> &'static str {
+        match self {
+            DayPeriod::Morning => "hello.morning",
+            DayPeriod::Afternoon => "hello.afternoon",
+            DayPeriod::Evening => "hello.evening",
+        }
+    }
+}
+
+/// Holidays with their own salutation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Holiday {
+    /// January 1
+    NewYear,
+    /// Easter Sunday in the Gregorian calendar
+    Easter,
+    /// December 25
+    Christmas,
+}
+
+impl Holiday {
+    /// The holiday falling on `date`, if any
+    pub fn on(date: CivilDate) -> Option<Holiday> {
+        match (date.month, date.day) {
+            (1, 1) => Some(Holiday::NewYear),
+            (12, 25) => Some(Holiday::Christmas),
+            _ if date == easter(date.year) => Some(Holiday::Easter),
+            _ => None,
+        }
+    }
+
+    fn message_key(self) -> &'static str {
+        match self {
+            Holiday::NewYear => "holiday.new_year",
+            Holiday::Easter => "holiday.easter",
+            Holiday::Christmas => "holiday.christmas",
+        }
+    }
+}
+
+/// The date of Easter Sunday in `year`, using the anonymous Gregorian
+/// algorithm
+///
+/// ```
+/// use hello_rs::calendar::easter;
+/// use hello_rs::CivilDate;
+///
+/// assert_eq!(easter(2024), CivilDate { year: 2024, month: 3, day: 31 });
+/// ```
+pub fn easter(year: i32) -> CivilDate {
+    let a = year.rem_euclid(19);
+    let b = year.div_euclid(100);
+    let c = year.rem_euclid(100);
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15).rem_euclid(30);
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k).rem_euclid(7);
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    CivilDate {
+        year,
+        month: month as u8,
+        day: day as u8,
+    }
+}
+
+/// The salutation for `time` in `locale`: a holiday greeting if the local
+/// date is a holiday, otherwise one for the part of the day
+///
+/// ```
+/// use hello_rs::calendar::salutation;
+/// use hello_rs::{Locale, UtcOffset, ZonedTime};
+///
+/// // 2024-06-03T08:00:00Z
+/// let morning = ZonedTime::new(1_717_401_600, UtcOffset::UTC);
+/// assert_eq!(salutation(morning, &Locale::default()), "good morning");
+/// ```
+pub fn salutation(time: ZonedTime, locale: &Locale) -> &'static str {
+    let key = match Holiday::on(time.date()) {
+        Some(holiday) => holiday.message_key(),
+        None => DayPeriod::of_hour(time.hour()).message_key(),
+    };
+    catalog::message(locale, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UtcOffset;
+
+    #[test]
+    fn test_easter() {
+        let known = [
+            (1961, 4, 2),
+            (2000, 4, 23),
+            (2019, 4, 21),
+            (2024, 3, 31),
+            (2025, 4, 20),
+            (2038, 4, 25),
+        ];
+        for (year, month, day) in known {
+            assert_eq!(easter(year), CivilDate { year, month, day });
+        }
+    }
+
+    #[test]
+    fn test_holidays() {
+        let on = |month, day| {
+            Holiday::on(CivilDate {
+                year: 2025,
+                month,
+                day,
+            })
+        };
+        assert_eq!(on(1, 1), Some(Holiday::NewYear));
+        assert_eq!(on(4, 20), Some(Holiday::Easter));
+        assert_eq!(on(12, 25), Some(Holiday::Christmas));
+        assert_eq!(on(7, 4), None);
+    }
+
+    #[test]
+    fn test_day_periods() {
+        assert_eq!(DayPeriod::of_hour(4), DayPeriod::Evening);
+        assert_eq!(DayPeriod::of_hour(5), DayPeriod::Morning);
+        assert_eq!(DayPeriod::of_hour(12), DayPeriod::Afternoon);
+        assert_eq!(DayPeriod::of_hour(18), DayPeriod::Evening);
+    }
+
+    #[test]
+    fn test_salutation() {
+        // 2024-06-03T15:00:00Z
+        let afternoon = ZonedTime::new(1_717_426_800, UtcOffset::UTC);
+        let de = Locale::parse("de").unwrap();
+        assert_eq!(salutation(afternoon, &de), "guten Tag");
+        let evening_in_tokyo = ZonedTime::new(1_717_426_800, UtcOffset::from_hours(9));
+        assert_eq!(salutation(evening_in_tokyo, &de), "guten Abend");
+
+        // 2024-12-25T10:00:00Z
+        let christmas = ZonedTime::new(1_735_120_800, UtcOffset::UTC);
+        assert_eq!(salutation(christmas, &Locale::default()), "merry Christmas");
+        assert_eq!(
+            salutation(christmas, &Locale::parse("es").unwrap()),
+            "feliz Navidad"
+        );
+    }
+}
@@ -0,0 +1,300 @@
+//! The message catalog bundled with the crate.
+//!
+//! Messages are compiled into the binary as a static table so that lookups
+//! need no I/O and no external dependencies. Base languages must define every
+//! key that English defines; regional tables only list what differs from
+//! their base language. Keys ending in `.variants` hold a `|`-separated list.
+
+use crate::Locale;
+
+type Messages = &'static [(&'static str, &'static str)];
+
+static CATALOG: &[(&str, Messages)] = &[
+    (
+        "en",
+        &[
+            ("hello", "hello"),
+            ("hello.intimate", "hiya"),
+            ("hello.casual", "hey"),
+            ("hello.formal", "good day"),
+            ("hello.ceremonial", "greetings"),
+            ("hello.morning", "good morning"),
+            ("hello.afternoon", "good afternoon"),
+            ("hello.evening", "good evening"),
+            ("holiday.new_year", "happy new year"),
+            ("holiday.easter", "happy Easter"),
+            ("holiday.christmas", "merry Christmas"),
+            ("welcome.feminine", "welcome"),
+            ("welcome.masculine", "welcome"),
+            ("welcome.neutral", "welcome"),
+            (
+                "hello.variants",
+                "hi|hey there|greetings|hello|good to see you",
+            ),
+            ("you.familiar", "you"),
+            ("you.polite", "you"),
+            ("how_are_you.familiar", "how are you?"),
+            ("how_are_you.polite", "how are you?"),
+            ("list.two", " and "),
+            ("list.end", ", and "),
+            ("list.others", "{n} others"),
+        ],
+    ),
+    (
+        "de",
+        &[
+            ("hello", "hallo"),
+            ("hello.intimate", "na"),
+            ("hello.casual", "hi"),
+            ("hello.formal", "guten Tag"),
+            ("hello.ceremonial", "ich grüße Sie"),
+            ("hello.morning", "guten Morgen"),
+            ("hello.afternoon", "guten Tag"),
+            ("hello.evening", "guten Abend"),
+            ("holiday.new_year", "frohes neues Jahr"),
+            ("holiday.easter", "frohe Ostern"),
+            ("holiday.christmas", "frohe Weihnachten"),
+            ("welcome.feminine", "willkommen"),
+            ("welcome.masculine", "willkommen"),
+            ("welcome.neutral", "willkommen"),
+            ("hello.variants", "hi|hallo|servus|grüß dich|moin"),
+            ("you.familiar", "du"),
+            ("you.polite", "Sie"),
+            ("how_are_you.familiar", "wie geht's dir?"),
+            ("how_are_you.polite", "wie geht es Ihnen?"),
+            ("list.two", " und "),
+            ("list.end", " und "),
+            ("list.others", "{n} weitere"),
+        ],
+    ),
+    (
+        "es",
+        &[
+            ("hello", "hola"),
+            ("hello.intimate", "holi"),
+            ("hello.casual", "qué tal"),
+            ("hello.formal", "saludos"),
+            ("hello.ceremonial", "le saludo"),
+            ("hello.morning", "buenos días"),
+            ("hello.afternoon", "buenas tardes"),
+            ("hello.evening", "buenas noches"),
+            ("holiday.new_year", "feliz año nuevo"),
+            ("holiday.easter", "felices Pascuas"),
+            ("holiday.christmas", "feliz Navidad"),
+            ("welcome.feminine", "bienvenida"),
+            ("welcome.masculine", "bienvenido"),
+            ("welcome.neutral", "bienvenide"),
+            ("hello.variants", "hola|buenas|qué tal|saludos|hola, hola"),
+            ("you.familiar", "tú"),
+            ("you.polite", "usted"),
+            ("how_are_you.familiar", "¿cómo estás?"),
+            ("how_are_you.polite", "¿cómo está usted?"),
+            ("list.two", " y "),
+            ("list.end", " y "),
+            ("list.others", "{n} más"),
+        ],
+    ),
+    (
+        "fr",
+        &[
+            ("hello", "bonjour"),
+            ("hello.intimate", "coucou"),
+            ("hello.casual", "salut"),
+            ("hello.formal", "bonjour"),
+            ("hello.ceremonial", "je vous salue"),
+            ("hello.morning", "bonjour"),
+            ("hello.afternoon", "bonjour"),
+            ("hello.evening", "bonsoir"),
+            ("holiday.new_year", "bonne année"),
+            ("holiday.easter", "joyeuses Pâques"),
+            ("holiday.christmas", "joyeux Noël"),
+            ("welcome.feminine", "bienvenue"),
+            ("welcome.masculine", "bienvenu"),
+            ("welcome.neutral", "bienvenu·e"),
+            ("hello.variants", "salut|bonjour|coucou|bonjour à toi|hé"),
+            ("you.familiar", "tu"),
+            ("you.polite", "vous"),
+            ("how_are_you.familiar", "comment vas-tu ?"),
+            ("how_are_you.polite", "comment allez-vous ?"),
+            ("list.two", " et "),
+            ("list.end", " et "),
+            ("list.others", "{n} autres"),
+        ],
+    ),
+    (
+        "it",
+        &[
+            ("hello", "ciao"),
+            ("hello.intimate", "ehilà"),
+            ("hello.casual", "ehi"),
+            ("hello.formal", "buongiorno"),
+            ("hello.ceremonial", "la saluto"),
+            ("hello.morning", "buongiorno"),
+            ("hello.afternoon", "buon pomeriggio"),
+            ("hello.evening", "buonasera"),
+            ("holiday.new_year", "buon anno"),
+            ("holiday.easter", "buona Pasqua"),
+            ("holiday.christmas", "buon Natale"),
+            ("welcome.feminine", "benvenuta"),
+            ("welcome.masculine", "benvenuto"),
+            ("welcome.neutral", "benvenutə"),
+            ("hello.variants", "ciao|salve|ehi|buongiorno|ciao a te"),
+            ("you.familiar", "tu"),
+            ("you.polite", "Lei"),
+            ("how_are_you.familiar", "come stai?"),
+            ("how_are_you.polite", "come sta?"),
+            ("list.two", " e "),
+            ("list.end", " e "),
+            ("list.others", "altri {n}"),
+        ],
+    ),
+    (
+        "nl",
+        &[
+            ("hello", "hallo"),
+            ("hello.intimate", "hé"),
+            ("hello.casual", "hoi"),
+            ("hello.formal", "goedendag"),
+            ("hello.ceremonial", "ik groet u"),
+            ("hello.morning", "goedemorgen"),
+            ("hello.afternoon", "goedemiddag"),
+            ("hello.evening", "goedenavond"),
+            ("holiday.new_year", "gelukkig nieuwjaar"),
+            ("holiday.easter", "vrolijk Pasen"),
+            ("holiday.christmas", "vrolijk kerstfeest"),
+            ("welcome.feminine", "welkom"),
+            ("welcome.masculine", "welkom"),
+            ("welcome.neutral", "welkom"),
+            ("hello.variants", "hoi|hallo|hé|goedendag|dag"),
+            ("you.familiar", "jij"),
+            ("you.polite", "u"),
+            ("how_are_you.familiar", "hoe gaat het met je?"),
+            ("how_are_you.polite", "hoe gaat het met u?"),
+            ("list.two", " en "),
+            ("list.end", " en "),
+            ("list.others", "{n} anderen"),
+        ],
+    ),
+    (
+        "pt",
+        &[
+            ("hello", "olá"),
+            ("hello.intimate", "e aí"),
+            ("hello.casual", "oi"),
+            ("hello.formal", "cumprimentos"),
+            ("hello.ceremonial", "saudações"),
+            ("hello.morning", "bom dia"),
+            ("hello.afternoon", "boa tarde"),
+            ("hello.evening", "boa noite"),
+            ("holiday.new_year", "feliz ano novo"),
+            ("holiday.easter", "feliz Páscoa"),
+            ("holiday.christmas", "feliz Natal"),
+            ("welcome.feminine", "bem-vinda"),
+            ("welcome.masculine", "bem-vindo"),
+            ("welcome.neutral", "bem-vinde"),
+            ("hello.variants", "olá|oi|bom dia|saudações|e aí"),
+            ("you.familiar", "tu"),
+            ("you.polite", "você"),
+            ("how_are_you.familiar", "como estás?"),
+            ("how_are_you.polite", "como está?"),
+            ("list.two", " e "),
+            ("list.end", " e "),
+            ("list.others", "mais {n}"),
+        ],
+    ),
+    (
+        "pt-BR",
+        &[
+            ("you.familiar", "você"),
+            ("you.polite", "o senhor"),
+            ("how_are_you.familiar", "como você está?"),
+            ("how_are_you.polite", "como o senhor está?"),
+        ],
+    ),
+    (
+        "tr",
+        &[
+            ("hello", "merhaba"),
+            ("hello.intimate", "naber"),
+            ("hello.casual", "selam"),
+            ("hello.formal", "iyi günler"),
+            ("hello.ceremonial", "saygılar"),
+            ("hello.morning", "günaydın"),
+            ("hello.afternoon", "iyi günler"),
+            ("hello.evening", "iyi akşamlar"),
+            ("holiday.new_year", "mutlu yıllar"),
+            ("holiday.easter", "mutlu Paskalyalar"),
+            ("holiday.christmas", "mutlu Noeller"),
+            ("welcome.feminine", "hoş geldiniz"),
+            ("welcome.masculine", "hoş geldiniz"),
+            ("welcome.neutral", "hoş geldiniz"),
+            ("hello.variants", "merhaba|selam|selamlar|iyi günler|naber"),
+            ("you.familiar", "sen"),
+            ("you.polite", "siz"),
+            ("how_are_you.familiar", "nasılsın?"),
+            ("how_are_you.polite", "nasılsınız?"),
+            ("list.two", " ve "),
+            ("list.end", " ve "),
+            ("list.others", "{n} kişi daha"),
+        ],
+    ),
+];
+
+/// The tags that have a table in the catalog
+pub(crate) fn locales() -> impl Iterator<Item = &'static str> {
+    CATALOG.iter().map(|(tag, _)| *tag)
+}
+
+/// Look up `key`, walking the locale's fallback chain.
+///
+/// Returns the key itself if no table defines it, which only happens for
+/// keys English doesn't define either.
+pub(crate) fn message(locale: &Locale, key: &'static str) -> &'static str {
+    locale
+        .fallback_chain()
+        .find_map(|tag| table(tag)?.iter().find(|(k, _)| *k == key))
+        .map_or(key, |(_, text)| text)
+}
+
+fn table(tag: &str) -> Option<Messages> {
+    CATALOG.iter().find(|(t, _)| *t == tag).map(|(_, m)| *m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_languages_are_complete() {
+        let english = table("en").unwrap();
+        for (tag, messages) in CATALOG.iter().filter(|(tag, _)| !tag.contains('-')) {
+            for (key, _) in english {
+                assert!(
+                    messages.iter().any(|(k, _)| k == key),
+                    "{tag} is missing {key:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_fallback() {
+        let pt_br = Locale::parse("pt-BR").unwrap();
+        assert_eq!(message(&pt_br, "you.polite"), "o senhor");
+        assert_eq!(message(&pt_br, "hello"), "olá");
+        assert_eq!(message(&Locale::parse("xx").unwrap(), "hello"), "hello");
+        assert_eq!(message(&Locale::default(), "no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn test_formal_is_not_time_of_day() {
+        for tag in ["es", "pt"] {
+            let locale = Locale::parse(tag).unwrap();
+            let formal = message(&locale, "hello.formal");
+            for key in ["hello.morning", "hello.afternoon", "hello.evening"] {
+                assert_ne!(formal, message(&locale, key), "{tag} {key}");
+            }
+            assert_ne!(message(&locale, "hello.casual"), message(&locale, "hello"));
+        }
+    }
+}
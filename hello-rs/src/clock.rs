@@ -0,0 +1,294 @@
+//! Where greetings get the current time from.
+//!
+//! Time zones are represented by a fixed [`UtcOffset`] rather than a time
+//! zone database, so that greeting a user needs no data files and gives the
+//! same answer on every platform.
+
+/// A fixed offset from UTC, e.g. +05:30.
+///
+/// Offsets are clamped to ±18 hours, the range ISO 8601 and most time
+/// libraries accept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct UtcOffset {
+    minutes: i32,
+}
+
+impl UtcOffset {
+    /// Coordinated Universal Time
+    pub const UTC: UtcOffset = UtcOffset { minutes: 0 };
+
+    /// The largest offset, +18:00; the smallest is its negation
+    pub const MAX: UtcOffset = UtcOffset {
+        minutes: MAX_OFFSET_MINUTES,
+    };
+
+    /// An offset of `minutes` east of UTC; negative values are west
+    pub const fn from_minutes(minutes: i32) -> Self {
+        let minutes = if minutes > MAX_OFFSET_MINUTES {
+            MAX_OFFSET_MINUTES
+        } else if minutes < -MAX_OFFSET_MINUTES {
+            -MAX_OFFSET_MINUTES
+        } else {
+            minutes
+        };
+        UtcOffset { minutes }
+    }
+
+    /// An offset of whole `hours` east of UTC
+    pub const fn from_hours(hours: i32) -> Self {
+        Self::from_minutes(hours.saturating_mul(60))
+    }
+
+    /// Minutes east of UTC
+    pub const fn minutes(self) -> i32 {
+        self.minutes
+    }
+}
+
+const MAX_OFFSET_MINUTES: i32 = 18 * 60;
+
+/// Deserialized from minutes east of UTC through [`UtcOffset::from_minutes`],
+/// so out-of-range offsets are clamped like any other.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for UtcOffset {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        i32::deserialize(deserializer).map(UtcOffset::from_minutes)
+    }
+}
+
+/// A date in the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CivilDate {
+    /// The year, e.g. 2024
+    pub year: i32,
+    /// The month, 1 through 12
+    pub month: u8,
+    /// The day of the month, 1 through 31
+    pub day: u8,
+}
+
+impl CivilDate {
+    /// The date `days` days after 1970-01-01
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        // Howard Hinnant's civil_from_days
+        let z = days.saturating_add(719_468);
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        // Days this far from the epoch are only reachable by saturating, and
+        // their year is clamped to what fits
+        let year = i32::try_from(year).unwrap_or(if year < 0 { i32::MIN } else { i32::MAX });
+        CivilDate { year, month, day }
+    }
+}
+
+/// An instant in time, read in a particular UTC offset.
+///
+/// ```
+/// use hello_rs::{CivilDate, UtcOffset, ZonedTime};
+///
+/// // 2024-12-24T23:30:00Z is already Christmas morning in Tokyo
+/// let time = ZonedTime::new(1_735_083_000, UtcOffset::from_hours(9));
+/// assert_eq!(time.date(), CivilDate { year: 2024, month: 12, day: 25 });
+/// assert_eq!(time.hour(), 8);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct ZonedTime {
+    unix_seconds: i64,
+    offset: UtcOffset,
+}
+
+impl ZonedTime {
+    /// `unix_seconds` after the Unix epoch, read at `offset`
+    pub const fn new(unix_seconds: i64, offset: UtcOffset) -> Self {
+        ZonedTime {
+            unix_seconds,
+            offset,
+        }
+    }
+
+    /// Seconds since 1970-01-01T00:00:00Z
+    pub const fn unix_seconds(&self) -> i64 {
+        self.unix_seconds
+    }
+
+    /// The offset the time is read in
+    pub const fn offset(&self) -> UtcOffset {
+        self.offset
+    }
+
+    /// Seconds since the epoch on the local wall clock, saturating at the
+    /// ends of the `i64` range rather than overflowing
+    fn local_seconds(&self) -> i64 {
+        self.unix_seconds
+            .saturating_add(i64::from(self.offset.minutes) * 60)
+    }
+
+    /// The local calendar date
+    pub fn date(&self) -> CivilDate {
+        CivilDate::from_days_since_epoch(self.local_seconds().div_euclid(86_400))
+    }
+
+    /// The local hour, 0 through 23
+    pub fn hour(&self) -> u8 {
+        (self.local_seconds().rem_euclid(86_400) / 3600) as u8
+    }
+}
+
+/// A source of the current time.
+///
+/// Greetings take a `Clock` instead of reading the system time directly, so
+/// tests can use a [`FixedClock`] and hosts such as a wasm runtime can supply
+/// their own.
+pub trait Clock {
+    /// The current time
+    fn now(&self) -> ZonedTime;
+}
+
+/// A clock that is always at the same instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub ZonedTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> ZonedTime {
+        self.0
+    }
+}
+
+/// The operating system's clock, read at a fixed UTC offset.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SystemClock {
+    offset: UtcOffset,
+}
+
+#[cfg(feature = "std")]
+impl SystemClock {
+    /// Read the system clock at `offset`
+    pub const fn new(offset: UtcOffset) -> Self {
+        SystemClock { offset }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> ZonedTime {
+        let unix_seconds = match std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)
+        {
+            Ok(since) => since.as_secs() as i64,
+            Err(before) => -(before.duration().as_secs() as i64),
+        };
+        ZonedTime::new(unix_seconds, self.offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_date() {
+        let date = |days| CivilDate::from_days_since_epoch(days);
+        assert_eq!(
+            date(0),
+            CivilDate {
+                year: 1970,
+                month: 1,
+                day: 1
+            }
+        );
+        assert_eq!(
+            date(-1),
+            CivilDate {
+                year: 1969,
+                month: 12,
+                day: 31
+            }
+        );
+        assert_eq!(
+            date(11_016),
+            CivilDate {
+                year: 2000,
+                month: 2,
+                day: 29
+            }
+        );
+        assert_eq!(
+            date(19_723),
+            CivilDate {
+                year: 2024,
+                month: 1,
+                day: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_offsets() {
+        // 2024-01-01T02:00:00Z
+        let instant = 1_704_074_400;
+        let utc = ZonedTime::new(instant, UtcOffset::UTC);
+        assert_eq!((utc.date().day, utc.hour()), (1, 2));
+        let new_york = ZonedTime::new(instant, UtcOffset::from_hours(-5));
+        assert_eq!((new_york.date().day, new_york.hour()), (31, 21));
+        let india = ZonedTime::new(instant, UtcOffset::from_minutes(330));
+        assert_eq!(india.hour(), 7);
+    }
+
+    #[test]
+    fn test_extreme_inputs() {
+        assert_eq!(UtcOffset::from_minutes(i32::MAX), UtcOffset::MAX);
+        assert_eq!(UtcOffset::from_minutes(i32::MIN).minutes(), -18 * 60);
+        assert_eq!(UtcOffset::from_hours(i32::MAX), UtcOffset::MAX);
+        assert_eq!(UtcOffset::from_hours(-19).minutes(), -18 * 60);
+        assert_eq!(UtcOffset::from_hours(14).minutes(), 14 * 60);
+
+        for unix_seconds in [i64::MAX, i64::MIN] {
+            for offset in [UtcOffset::MAX, UtcOffset::from_hours(-18), UtcOffset::UTC] {
+                let time = ZonedTime::new(unix_seconds, offset);
+                assert!(time.hour() < 24);
+                assert!((1..=12).contains(&time.date().month));
+            }
+        }
+        assert_eq!(
+            ZonedTime::new(i64::MAX, UtcOffset::UTC).date().year,
+            i32::MAX
+        );
+        assert_eq!(
+            ZonedTime::new(i64::MIN, UtcOffset::UTC).date().year,
+            i32::MIN
+        );
+        assert_eq!(CivilDate::from_days_since_epoch(i64::MAX).year, i32::MAX);
+        assert_eq!(CivilDate::from_days_since_epoch(i64::MIN).year, i32::MIN);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_offset() {
+        let offset = |json| serde_json::from_str::<UtcOffset>(json).unwrap();
+        assert_eq!(offset("330"), UtcOffset::from_minutes(330));
+        assert_eq!(offset("100000"), UtcOffset::MAX);
+        assert_eq!(offset("-100000").minutes(), -18 * 60);
+        assert_eq!(serde_json::to_string(&UtcOffset::MAX).unwrap(), "1080");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_system_clock() {
+        let now = SystemClock::default().now();
+        assert!(now.date().year >= 2024);
+        assert_eq!(now.offset(), UtcOffset::UTC);
+    }
+}
@@ -0,0 +1,226 @@
+//! Greeting preferences that can be stored in a config file.
+//!
+//! With the `serde` feature, [`GreetingConfig`] and the types it is made of
+//! implement `Serialize` and `Deserialize`. The schema is part of the public
+//! API: field names and values only change in a breaking release.
+
+use alloc::string::String;
+
+use crate::{
+    BidiIsolation, Capitalization, Formality, Format, Gender, Greeting, Locale, PersonName, Render,
+    Template,
+};
+
+/// How someone likes to be greeted.
+///
+/// Every field may be left out of a config and takes its default. In JSON:
+///
+/// ```json
+/// {
+///   "locale": "de",
+///   "formality": "formal",
+///   "capitalization": "sentence",
+///   "punctuation": "!",
+///   "gender": "neutral",
+///   "welcome": false,
+///   "how_are_you": false,
+///   "bidi": { "isolate": true, "strip_controls": false },
+///   "template": null,
+///   "format": "plain"
+/// }
+/// ```
+///
+/// or the same in TOML:
+///
+/// ```toml
+/// locale = "de"
+/// formality = "formal"
+/// capitalization = "sentence"
+/// punctuation = "!"
+/// ```
+///
+/// | Field            | Values                                                        | Default     |
+/// |------------------|---------------------------------------------------------------|-------------|
+/// | `locale`         | a language tag, e.g. `"pt-BR"`                                | `"en"`      |
+/// | `formality`      | `intimate`, `casual`, `neutral`, `formal`, `ceremonial`       | `"neutral"` |
+/// | `capitalization` | `as_is`, `lower`, `upper`, `sentence`, `title`                | `"as_is"`   |
+/// | `punctuation`    | any string                                                    | `""`        |
+/// | `gender`         | `feminine`, `masculine`, `neutral`                            | `"neutral"` |
+/// | `welcome`        | `true` or `false`                                             | `false`     |
+/// | `how_are_you`    | `true` or `false`                                             | `false`     |
+/// | `bidi`           | `null`, or `isolate` and `strip_controls` booleans            | `null`      |
+/// | `template`       | `null`, or a [`Template`] source                              | `null`      |
+/// | `format`         | `plain`, `html`, `markdown`, `ansi`, `json`                   | `"plain"`   |
+///
+/// ```
+/// use hello_rs::{Capitalization, Formality, GreetingConfig};
+///
+/// let config = GreetingConfig {
+///     formality: Formality::Formal,
+///     capitalization: Capitalization::Sentence,
+///     punctuation: ".".into(),
+///     ..GreetingConfig::default()
+/// };
+/// assert_eq!(config.greet("Dr. Chidi Okafor"), "Good day, Dr. Okafor.");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct GreetingConfig {
+    /// The language of the greeting
+    pub locale: Locale,
+    /// How formal the greeting is, which also decides how the name is
+    /// addressed
+    pub formality: Formality,
+    /// How the greeting is capitalized
+    pub capitalization: Capitalization,
+    /// Punctuation after the name
+    pub punctuation: String,
+    /// The recipient's grammatical gender, which `welcome` agrees with
+    pub gender: Gender,
+    /// Whether to welcome the recipient instead of greeting them
+    pub welcome: bool,
+    /// Whether to follow the greeting with "how are you?"
+    pub how_are_you: bool,
+    /// Whether and how the name is isolated from the rest of the greeting
+    pub bidi: Option<BidiIsolation>,
+    /// A template to render with instead of `format`
+    pub template: Option<Template>,
+    /// How the greeting is rendered when there is no template
+    pub format: Format,
+}
+
+impl GreetingConfig {
+    /// The greeting for `name`, which is parsed as a [`PersonName`] and
+    /// addressed as the formality calls for
+    pub fn greeting(&self, name: &str) -> Greeting {
+        let person = PersonName::parse(name, &self.locale);
+        let mut builder = Greeting::for_person(person)
+            .locale(self.locale.clone())
+            .formality(self.formality)
+            .capitalization(self.capitalization)
+            .punctuation(self.punctuation.clone())
+            .gender(self.gender)
+            .welcome(self.welcome)
+            .how_are_you(self.how_are_you);
+        if let Some(bidi) = self.bidi {
+            builder = builder.bidi(bidi);
+        }
+        builder.build()
+    }
+
+    /// Greet `name`, rendered with the template if there is one and in the
+    /// configured format otherwise
+    pub fn greet(&self, name: &str) -> String {
+        let greeting = self.greeting(name);
+        match &self.template {
+            Some(template) => template.render(&greeting),
+            None => self.format.render(&greeting),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(GreetingConfig::default().greet("ana"), crate::hello("ana"));
+    }
+
+    #[test]
+    fn test_template() {
+        let config = GreetingConfig {
+            formality: Formality::Casual,
+            template: Some(Template::compile("{salutation|title}, {name}{punct}").unwrap()),
+            punctuation: "!".into(),
+            ..GreetingConfig::default()
+        };
+        assert_eq!(config.greet("Dr. Chidi Okafor"), "Hey, Chidi!");
+    }
+
+    #[test]
+    fn test_welcome() {
+        let config = |gender| GreetingConfig {
+            locale: Locale::parse("es").unwrap(),
+            gender,
+            welcome: true,
+            ..GreetingConfig::default()
+        };
+        assert_eq!(config(Gender::Feminine).greet("ana"), "bienvenida ana");
+        assert_eq!(config(Gender::Masculine).greet("ana"), "bienvenido ana");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_schema() {
+        let config: GreetingConfig = serde_json::from_str(
+            r#"{
+                "locale": "pt-BR",
+                "formality": "casual",
+                "capitalization": "sentence",
+                "template": "{salutation} {name|upper}",
+                "bidi": {"strip_controls": true}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.locale, Locale::parse("pt-BR").unwrap());
+        assert_eq!(config.bidi, Some(BidiIsolation::new().strip_controls(true)));
+        assert_eq!(config.greet("ana silva"), "oi ANA");
+        assert_eq!(config.greet("an\u{202E}a silva"), "oi ANA");
+
+        let json = serde_json::to_value(GreetingConfig::default()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "locale": "en",
+                "formality": "neutral",
+                "capitalization": "as_is",
+                "punctuation": "",
+                "gender": "neutral",
+                "welcome": false,
+                "how_are_you": false,
+                "bidi": null,
+                "template": null,
+                "format": "plain",
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_errors() {
+        let error = |json| {
+            serde_json::from_str::<GreetingConfig>(json)
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(error(r#"{"locale": "not a tag"}"#).contains("invalid locale tag"));
+        assert!(error(r#"{"template": "{nmae}"}"#).contains("unknown placeholder"));
+        assert!(error(r#"{"formality": "posh"}"#).contains("unknown variant"));
+        assert!(error(r#"{"formalty": "formal"}"#).contains("unknown field"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_greeting_round_trip() {
+        let greeting = Greeting::builder("ana")
+            .formality(Formality::Formal)
+            .time(crate::ZonedTime::new(
+                1_717_441_200,
+                crate::UtcOffset::from_hours(2),
+            ))
+            .bidi(BidiIsolation::new())
+            .build();
+        let json = serde_json::to_string(&greeting).unwrap();
+        assert_eq!(serde_json::from_str::<Greeting>(&json).unwrap(), greeting);
+
+        let minimal: Greeting = serde_json::from_str(r#"{"recipient": "bo"}"#).unwrap();
+        assert_eq!(minimal, Greeting::new("bo"));
+        assert!(serde_json::from_str::<Greeting>("{}").is_err());
+    }
+}
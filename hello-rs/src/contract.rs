@@ -0,0 +1,384 @@
+//! The promises hello-rs makes to the crates that wrap it, as code.
+//!
+//! hello-py and hello-wasm rely on [`hello`](crate::hello) returning
+//! exactly `"hello {name}"`: a lowercase "hello", one space, then the name
+//! byte for byte, so that they can do their own capitalization and
+//! punctuation. Calling [`verify`] from a downstream test means a hello-rs
+//! release that breaks that format fails the downstream build too.
+//!
+//! ```
+//! hello_rs::contract::verify(hello_rs::hello);
+//! ```
+//!
+//! Beyond the plain greeting, [`VectorFile`] holds golden vectors for every
+//! built-in style and several locales, loaded from the versioned
+//! `vectors/greetings.json` that ships with the crate. hello-rs, hello-py and
+//! hello-wasm all replay that one file, so the bindings can't drift apart.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Locale, StyleRegistry};
+
+/// A name and the greeting hello-rs promises for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Vector {
+    /// The name passed in
+    pub name: &'static str,
+    /// The exact greeting expected back
+    pub greeting: &'static str,
+}
+
+const fn vector(name: &'static str, greeting: &'static str) -> Vector {
+    Vector { name, greeting }
+}
+
+/// The golden vectors [`check`] and [`verify`] run through.
+///
+/// They cover the parts of the format downstream crates depend on: the
+/// salutation stays lowercase, and the name is neither trimmed, recased nor
+/// normalized.
+pub const VECTORS: &[Vector] = &[
+    vector("world", "hello world"),
+    vector("claude", "hello claude"),
+    vector("rust wasm", "hello rust wasm"),
+    vector("Ana", "hello Ana"),
+    vector("ANA", "hello ANA"),
+    vector("o'neill", "hello o'neill"),
+    vector("  ana  ", "hello   ana  "),
+    vector("", "hello "),
+    vector("محمد", "hello محمد"),
+    vector("张伟", "hello 张伟"),
+    vector("e\u{301}", "hello e\u{301}"),
+];
+
+/// A greeting that doesn't match what hello-rs promises.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    name: String,
+    style: String,
+    locale: Locale,
+    expected: String,
+    actual: String,
+}
+
+impl Violation {
+    fn plain(name: &str, expected: String, actual: String) -> Self {
+        Violation {
+            name: name.into(),
+            style: "plain".into(),
+            locale: Locale::default(),
+            expected,
+            actual,
+        }
+    }
+
+    /// The name that was greeted
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The style the name was greeted in, "plain" for [`hello`](crate::hello)
+    pub fn style(&self) -> &str {
+        &self.style
+    }
+
+    /// The locale the name was greeted in
+    pub fn locale(&self) -> &Locale {
+        &self.locale
+    }
+
+    /// The greeting the contract promises
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// The greeting that was produced instead
+    pub fn actual(&self) -> &str {
+        &self.actual
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} greeting for {:?} in {} should be {:?}, got {:?}",
+            self.style, self.name, self.locale, self.expected, self.actual
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Violation {}
+
+/// Check that `greeting` is what hello-rs promises for `name`
+///
+/// ```
+/// use hello_rs::contract::check_greeting;
+///
+/// assert!(check_greeting("ana", "hello ana").is_ok());
+/// assert!(check_greeting("ana", "Hello Ana!").is_err());
+/// ```
+pub fn check_greeting(name: &str, greeting: &str) -> Result<(), Violation> {
+    match greeting.strip_prefix("hello ") {
+        Some(rest) if rest == name => Ok(()),
+        _ => {
+            let mut expected = String::with_capacity("hello ".len() + name.len());
+            crate::write_hello(&mut expected, name).expect("writing to a String cannot fail");
+            Err(Violation::plain(name, expected, greeting.into()))
+        }
+    }
+}
+
+/// Run `greet` over [`VECTORS`], returning the first greeting that breaks
+/// the contract
+pub fn check<F, S>(mut greet: F) -> Result<(), Violation>
+where
+    F: FnMut(&str) -> S,
+    S: AsRef<str>,
+{
+    VECTORS.iter().try_for_each(|vector| {
+        let actual = greet(vector.name);
+        if actual.as_ref() == vector.greeting {
+            return Ok(());
+        }
+        Err(Violation::plain(
+            vector.name,
+            vector.greeting.into(),
+            actual.as_ref().into(),
+        ))
+    })
+}
+
+/// Like [`check`], but panic on a violation, for use in tests
+///
+/// # Panics
+///
+/// If `greet` breaks the contract for any of the [`VECTORS`].
+#[track_caller]
+pub fn verify<F, S>(greet: F)
+where
+    F: FnMut(&str) -> S,
+    S: AsRef<str>,
+{
+    if let Err(violation) = check(greet) {
+        panic!("hello-rs contract violated: {violation}");
+    }
+}
+
+/// The golden vector file bundled with hello-rs, `vectors/greetings.json`
+pub const VECTOR_FILE: &str = include_str!("../vectors/greetings.json");
+
+/// The version of the vector file format this hello-rs reads
+pub const VECTOR_FILE_VERSION: u32 = 1;
+
+/// One golden vector: a name greeted in a style and locale, and the exact
+/// greeting every binding must produce for it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GoldenVector {
+    /// The name passed in
+    pub name: String,
+    /// The locale to greet in
+    pub locale: Locale,
+    /// A style registered in [`StyleRegistry::default`]
+    pub style: String,
+    /// The exact greeting expected back
+    pub expected: String,
+}
+
+/// A versioned set of golden vectors, as stored in `vectors/greetings.json`:
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "vectors": [
+///     {"name": "world", "locale": "en", "style": "fancy", "expected": "Hello World!"}
+///   ]
+/// }
+/// ```
+///
+/// ```
+/// use hello_rs::contract::VectorFile;
+/// use hello_rs::StyleRegistry;
+///
+/// let styles = StyleRegistry::default();
+/// VectorFile::bundled().verify(|v| styles.greet(&v.style, &v.locale, &v.name).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VectorFile {
+    /// The format version, [`VECTOR_FILE_VERSION`] for files this hello-rs
+    /// can read
+    pub version: u32,
+    /// The vectors, in the order they appear in the file
+    pub vectors: Vec<GoldenVector>,
+}
+
+impl VectorFile {
+    /// The vectors bundled with hello-rs
+    pub fn bundled() -> Self {
+        VectorFile::parse(VECTOR_FILE).expect("the bundled vector file is valid")
+    }
+
+    /// Parse a vector file from JSON
+    pub fn parse(json: &str) -> Result<Self, VectorFileError> {
+        let file: VectorFile =
+            serde_json::from_str(json).map_err(|e| VectorFileError::Invalid {
+                message: e.to_string(),
+            })?;
+        if file.version != VECTOR_FILE_VERSION {
+            return Err(VectorFileError::UnsupportedVersion {
+                version: file.version,
+            });
+        }
+        Ok(file)
+    }
+
+    /// Run `greet` over the vectors, returning the first greeting that
+    /// doesn't match
+    pub fn check<F, S>(&self, mut greet: F) -> Result<(), Violation>
+    where
+        F: FnMut(&GoldenVector) -> S,
+        S: AsRef<str>,
+    {
+        self.vectors.iter().try_for_each(|vector| {
+            let actual = greet(vector);
+            if actual.as_ref() == vector.expected {
+                return Ok(());
+            }
+            Err(Violation {
+                name: vector.name.clone(),
+                style: vector.style.clone(),
+                locale: vector.locale.clone(),
+                expected: vector.expected.clone(),
+                actual: actual.as_ref().into(),
+            })
+        })
+    }
+
+    /// Like [`check`](VectorFile::check), but panic on a mismatch, for use
+    /// in tests
+    ///
+    /// # Panics
+    ///
+    /// If `greet` gets any of the vectors wrong.
+    #[track_caller]
+    pub fn verify<F, S>(&self, greet: F)
+    where
+        F: FnMut(&GoldenVector) -> S,
+        S: AsRef<str>,
+    {
+        if let Err(violation) = self.check(greet) {
+            panic!("hello-rs golden vector failed: {violation}");
+        }
+    }
+
+    /// Check hello-rs's own styles against the vectors
+    pub fn check_styles(&self, styles: &StyleRegistry) -> Result<(), Violation> {
+        self.check(|vector| {
+            styles
+                .greet(&vector.style, &vector.locale, &vector.name)
+                .unwrap_or_else(|e| e.to_string())
+        })
+    }
+}
+
+/// The error returned when a vector file can't be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VectorFileError {
+    /// The file isn't JSON, or doesn't match the vector file schema
+    Invalid {
+        /// What the JSON parser found wrong
+        message: String,
+    },
+    /// The file is in a format version this hello-rs doesn't read
+    UnsupportedVersion {
+        /// The version the file declares
+        version: u32,
+    },
+}
+
+impl fmt::Display for VectorFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VectorFileError::Invalid { message } => write!(f, "invalid vector file: {message}"),
+            VectorFileError::UnsupportedVersion { version } => write!(
+                f,
+                "unsupported vector file version {version}, expected {VECTOR_FILE_VERSION}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VectorFileError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vectors() {
+        for vector in VECTORS {
+            assert_eq!(check_greeting(vector.name, vector.greeting), Ok(()));
+        }
+        verify(crate::hello);
+        let mut buf = String::new();
+        verify(|name| crate::hello_into(&mut buf, name).to_string());
+    }
+
+    #[test]
+    fn test_violation() {
+        let violation = check(|name| crate::hello(name.trim())).unwrap_err();
+        assert_eq!(violation.name(), "  ana  ");
+        assert_eq!(violation.actual(), "hello ana");
+        assert_eq!(
+            violation.to_string(),
+            r#"plain greeting for "  ana  " in en should be "hello   ana  ", got "hello ana""#
+        );
+        assert_eq!(
+            check_greeting("bo", "hello  bo").unwrap_err().expected(),
+            "hello bo"
+        );
+    }
+
+    #[test]
+    fn test_vector_file() {
+        let file = VectorFile::bundled();
+        assert_eq!(file.version, VECTOR_FILE_VERSION);
+        assert_eq!(file.check_styles(&StyleRegistry::default()), Ok(()));
+        for style in StyleRegistry::default().names() {
+            assert!(
+                file.vectors.iter().any(|vector| vector.style == style),
+                "no vector for the {style:?} style"
+            );
+        }
+
+        let mut styles = StyleRegistry::default();
+        styles.register("fancy", crate::ShoutGreeter);
+        let violation = file.check_styles(&styles).unwrap_err();
+        assert_eq!(violation.style(), "fancy");
+        assert_eq!(violation.actual(), "HELLO WORLD!");
+    }
+
+    #[test]
+    fn test_vector_file_errors() {
+        assert_eq!(
+            VectorFile::parse(r#"{"version": 2, "vectors": []}"#),
+            Err(VectorFileError::UnsupportedVersion { version: 2 })
+        );
+        let error = VectorFile::parse(r#"{"version": 1}"#).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("invalid vector file: missing field"));
+    }
+
+    #[test]
+    #[should_panic(expected = "hello-rs contract violated")]
+    fn test_verify_panics() {
+        verify(|name| alloc::format!("Hello {name}!"));
+    }
+}
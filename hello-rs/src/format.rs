@@ -0,0 +1,558 @@
+//! Text formatting shared by every binding of hello-rs.
+//!
+//! Title-casing names well is harder than uppercasing the first `char` of
+//! each word: "o'neill" should become "O'Neill", "van der berg" keeps its
+//! lowercase particles, Turkish dotted i uppercases to "İ", Dutch "ij" is a
+//! single letter, and a word may begin with a decomposed accent. [`TitleCase`]
+//! handles all of these so the wasm and Python layers don't each need their
+//! own copy.
+//!
+//! ```
+//! use hello_rs::format::title_case;
+//!
+//! assert_eq!(title_case("hello o'neill"), "Hello O'Neill");
+//! assert_eq!(title_case("hello anne-marie van der berg"), "Hello Anne-Marie van der Berg");
+//! ```
+//!
+//! Lining greetings up in a table has the same problem: "李小龙" is three
+//! `char`s but six terminal columns, and a family emoji is five `char`s but
+//! two columns. [`display_width`] measures text the way a terminal draws it,
+//! and a [`Column`] truncates and pads to a width without ever splitting a
+//! grapheme cluster.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use crate::unicode::{cluster_width, graphemes};
+use crate::Locale;
+
+/// Name particles that stay lowercase unless they start the text
+pub const DEFAULT_PARTICLES: &[&str] = &[
+    "al", "bin", "da", "das", "de", "del", "della", "der", "di", "do", "dos", "du", "la", "le",
+    "van", "von",
+];
+
+/// Language-specific casing rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum CaseRules {
+    #[default]
+    Default,
+    /// Turkish and Azerbaijani, where i/İ and ı/I are separate letters
+    Turkic,
+    /// Dutch, where "ij" at the start of a word capitalizes as "IJ"
+    Dutch,
+}
+
+impl CaseRules {
+    pub(crate) fn for_locale(locale: &Locale) -> Self {
+        match locale.language() {
+            "tr" | "az" => CaseRules::Turkic,
+            "nl" => CaseRules::Dutch,
+            _ => CaseRules::Default,
+        }
+    }
+
+    /// Write the uppercase form of `c`
+    pub(crate) fn write_upper<W: Write + ?Sized>(self, c: char, out: &mut W) -> fmt::Result {
+        match c {
+            'i' if self == CaseRules::Turkic => out.write_char('İ'),
+            _ => c.to_uppercase().try_for_each(|u| out.write_char(u)),
+        }
+    }
+
+    /// Write the lowercase form of `c`
+    pub(crate) fn write_lower<W: Write + ?Sized>(self, c: char, out: &mut W) -> fmt::Result {
+        match c {
+            'I' if self == CaseRules::Turkic => out.write_char('ı'),
+            'İ' if self == CaseRules::Turkic => out.write_char('i'),
+            _ => c.to_lowercase().try_for_each(|l| out.write_char(l)),
+        }
+    }
+
+    /// Write the titlecase form of `c`, which differs from uppercase for
+    /// digraphs and ligatures
+    fn write_title<W: Write + ?Sized>(self, c: char, out: &mut W) -> fmt::Result {
+        match c {
+            'ß' => out.write_str("Ss"),
+            'Ǆ' | 'ǅ' | 'ǆ' => out.write_char('ǅ'),
+            'Ǉ' | 'ǈ' | 'ǉ' => out.write_char('ǈ'),
+            'Ǌ' | 'ǋ' | 'ǌ' => out.write_char('ǋ'),
+            'Ǳ' | 'ǲ' | 'ǳ' => out.write_char('ǲ'),
+            'ﬀ' => out.write_str("Ff"),
+            'ﬁ' => out.write_str("Fi"),
+            'ﬂ' => out.write_str("Fl"),
+            _ => self.write_upper(c, out),
+        }
+    }
+}
+
+/// Locale-sensitive, grapheme-aware title-casing.
+///
+/// Only the first letter of each word (and of each hyphenated part) is
+/// changed; the rest of the word is left as written so that names like
+/// "McDonald" survive.
+///
+/// ```
+/// use hello_rs::format::TitleCase;
+/// use hello_rs::Locale;
+///
+/// let turkish = TitleCase::for_locale(&Locale::parse("tr").unwrap());
+/// assert_eq!(turkish.apply("merhaba ismail"), "Merhaba İsmail");
+///
+/// let dutch = TitleCase::for_locale(&Locale::parse("nl").unwrap());
+/// assert_eq!(dutch.apply("hallo ijsbrand"), "Hallo IJsbrand");
+///
+/// let no_particles = TitleCase::new().particles(Vec::<String>::new());
+/// assert_eq!(no_particles.apply("hello van morrison"), "Hello Van Morrison");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Object))]
+pub struct TitleCase {
+    rules: CaseRules,
+    /// `None` stands for [`DEFAULT_PARTICLES`], so the common case needs no
+    /// allocation
+    particles: Option<Vec<String>>,
+}
+
+#[cfg_attr(feature = "uniffi", uniffi::export)]
+impl TitleCase {
+    /// Title-casing with the default rules and [`DEFAULT_PARTICLES`]
+    #[cfg_attr(feature = "uniffi", uniffi::constructor)]
+    pub fn new() -> Self {
+        TitleCase {
+            rules: CaseRules::Default,
+            particles: None,
+        }
+    }
+
+    /// Title-casing with the rules of `locale`'s language
+    #[cfg_attr(feature = "uniffi", uniffi::constructor)]
+    pub fn for_locale(locale: &Locale) -> Self {
+        TitleCase {
+            rules: CaseRules::for_locale(locale),
+            ..TitleCase::new()
+        }
+    }
+
+    /// Title-case `text` into a new string
+    pub fn apply(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        self.write(text, &mut out)
+            .expect("writing to a String cannot fail");
+        out
+    }
+}
+
+impl TitleCase {
+    /// Replace the words that stay lowercase when they aren't the first word
+    pub fn particles<I, S>(mut self, particles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.particles = Some(particles.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Title-case `text` into `out` without allocating
+    pub fn write<W: Write + ?Sized>(&self, text: &str, out: &mut W) -> fmt::Result {
+        self.write_with_rules(self.rules, text, out)
+    }
+
+    /// Title-case `text` into `out` with `rules` in place of the ones this
+    /// was created with, so callers can switch locale without rebuilding the
+    /// particle list
+    pub(crate) fn write_with_rules<W: Write + ?Sized>(
+        &self,
+        rules: CaseRules,
+        text: &str,
+        out: &mut W,
+    ) -> fmt::Result {
+        self.write_words(rules, text, true, out)
+    }
+
+    /// Title-case `text` into `out`. `first_word` is false when `text`
+    /// continues something already written, so a leading particle stays
+    /// lowercase.
+    pub(crate) fn write_words<W: Write + ?Sized>(
+        &self,
+        rules: CaseRules,
+        text: &str,
+        mut first_word: bool,
+        out: &mut W,
+    ) -> fmt::Result {
+        let mut rest = text;
+        while !rest.is_empty() {
+            let space_end = rest
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len());
+            out.write_str(&rest[..space_end])?;
+            rest = &rest[space_end..];
+
+            let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let word = &rest[..word_end];
+            if !word.is_empty() {
+                if !first_word && self.is_particle(word) {
+                    out.write_str(word)?;
+                } else {
+                    write_word(rules, word, out)?;
+                }
+                first_word = false;
+            }
+            rest = &rest[word_end..];
+        }
+        Ok(())
+    }
+
+    fn is_particle(&self, word: &str) -> bool {
+        match &self.particles {
+            Some(particles) => particles.iter().any(|p| p.eq_ignore_ascii_case(word)),
+            None => DEFAULT_PARTICLES
+                .iter()
+                .any(|p| p.eq_ignore_ascii_case(word)),
+        }
+    }
+}
+
+fn write_word<W: Write + ?Sized>(rules: CaseRules, word: &str, out: &mut W) -> fmt::Result {
+    let mut capitalize = true;
+    let mut letters_in_part = 0;
+    let mut clusters = graphemes(word).peekable();
+
+    while let Some(cluster) = clusters.next() {
+        let mut chars = cluster.chars();
+        let base = chars.next().unwrap_or_default();
+
+        if capitalize && base.is_alphabetic() {
+            let dutch_ij = rules == CaseRules::Dutch
+                && matches!(base, 'i' | 'I')
+                && clusters
+                    .peek()
+                    .is_some_and(|next| next.starts_with(['j', 'J']));
+            rules.write_title(base, out)?;
+            out.write_str(chars.as_str())?;
+            if dutch_ij {
+                let j = clusters.next().unwrap_or_default();
+                out.write_char('J')?;
+                out.write_str(&j[1..])?;
+            }
+            capitalize = false;
+        } else {
+            out.write_str(cluster)?;
+            if base.is_alphanumeric() {
+                capitalize = false;
+            }
+        }
+
+        if base.is_alphabetic() {
+            letters_in_part += 1;
+        }
+        match base {
+            '-' => {
+                capitalize = true;
+                letters_in_part = 0;
+            }
+            // "o'neill" and "d'angelo", but not "it's"
+            '\'' | '\u{2019}' if letters_in_part == 1 => {
+                capitalize = true;
+                letters_in_part = 0;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+impl Default for TitleCase {
+    fn default() -> Self {
+        TitleCase::new()
+    }
+}
+
+/// Title-case `text` with the default rules
+pub fn title_case(text: &str) -> String {
+    TitleCase::new().apply(text)
+}
+
+/// The number of terminal columns `text` takes
+///
+/// East Asian wide characters and emoji take two columns, combining marks
+/// and invisible formatting characters take none.
+///
+/// ```
+/// use hello_rs::format::display_width;
+///
+/// assert_eq!(display_width("Ana"), 3);
+/// assert_eq!(display_width("李小龙"), 6);
+/// assert_eq!(display_width("Zoe\u{308}"), 3);
+/// ```
+pub fn display_width(text: &str) -> usize {
+    graphemes(text).map(cluster_width).sum()
+}
+
+/// Where [`Column::pad`] places text that is narrower than the column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Align {
+    /// Padding goes on the right
+    #[default]
+    Left,
+    /// Padding goes on the left
+    Right,
+    /// Padding is split, with any odd column on the right
+    Center,
+}
+
+/// A fixed-width column of terminal or table output.
+///
+/// Text is measured with [`display_width`] and cut between grapheme
+/// clusters, so neither a CJK character nor an emoji sequence is ever split.
+///
+/// ```
+/// use hello_rs::format::{Align, Column};
+///
+/// let column = Column::new(8);
+/// assert_eq!(column.truncate("hello bartholomew"), "hello b…");
+/// assert_eq!(column.pad("hello"), "hello   ");
+/// assert_eq!(column.fit("hello 李小龙"), "hello … ");
+///
+/// let right = Column::new(8).ellipsis("...").align(Align::Right);
+/// assert_eq!(right.fit("hello bartholomew"), "hello...");
+/// assert_eq!(right.fit("hi"), "      hi");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Column {
+    width: usize,
+    /// `None` stands for [`Column::DEFAULT_ELLIPSIS`], so the common case
+    /// needs no allocation
+    ellipsis: Option<String>,
+    align: Align,
+}
+
+impl Column {
+    /// What truncated text ends with unless another ellipsis is set
+    pub const DEFAULT_ELLIPSIS: &'static str = "…";
+
+    /// A left-aligned column `width` terminal columns wide
+    pub fn new(width: usize) -> Self {
+        Column {
+            width,
+            ellipsis: None,
+            align: Align::Left,
+        }
+    }
+
+    /// Replace the text that marks a truncation; it may be empty
+    pub fn ellipsis(mut self, ellipsis: impl Into<String>) -> Self {
+        self.ellipsis = Some(ellipsis.into());
+        self
+    }
+
+    /// Choose where padding goes
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// The width of the column
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// `text` cut down to the column's width, ending in the ellipsis if
+    /// anything was removed
+    pub fn truncate(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        self.write_truncated(text, &mut out)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// `text` padded with spaces to the column's width. Wider text is left
+    /// as it is.
+    pub fn pad(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len().max(self.width));
+        self.write_padded(text, &mut out)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// `text` truncated and then padded, so it is exactly as wide as the
+    /// column
+    pub fn fit(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len().max(self.width));
+        self.write(text, &mut out)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// Write `text` into `out` cut down to the column's width
+    pub fn write_truncated<W: Write + ?Sized>(&self, text: &str, out: &mut W) -> fmt::Result {
+        let (kept, ellipsis, _) = self.cut(text);
+        out.write_str(kept)?;
+        out.write_str(ellipsis)
+    }
+
+    /// Write `text` into `out` padded to the column's width
+    pub fn write_padded<W: Write + ?Sized>(&self, text: &str, out: &mut W) -> fmt::Result {
+        self.write_aligned(&[text], display_width(text), out)
+    }
+
+    /// Write `text` into `out` truncated and padded to the column's width
+    pub fn write<W: Write + ?Sized>(&self, text: &str, out: &mut W) -> fmt::Result {
+        let (kept, ellipsis, width) = self.cut(text);
+        self.write_aligned(&[kept, ellipsis], width, out)
+    }
+
+    /// Split `text` into the prefix that fits and the ellipsis to follow it,
+    /// along with their combined width
+    fn cut<'a>(&'a self, text: &'a str) -> (&'a str, &'a str, usize) {
+        let width = display_width(text);
+        if width <= self.width {
+            return (text, "", width);
+        }
+        let ellipsis = self.ellipsis.as_deref().unwrap_or(Self::DEFAULT_ELLIPSIS);
+        let ellipsis_width = display_width(ellipsis);
+        // A column too narrow for the ellipsis gets as much text as fits
+        let (room, ellipsis, ellipsis_width) = match self.width.checked_sub(ellipsis_width) {
+            Some(room) => (room, ellipsis, ellipsis_width),
+            None => (self.width, "", 0),
+        };
+        let mut end = 0;
+        let mut used = 0;
+        for cluster in graphemes(text) {
+            let width = cluster_width(cluster);
+            if used + width > room {
+                break;
+            }
+            end += cluster.len();
+            used += width;
+        }
+        (&text[..end], ellipsis, used + ellipsis_width)
+    }
+
+    fn write_aligned<W: Write + ?Sized>(
+        &self,
+        parts: &[&str],
+        width: usize,
+        out: &mut W,
+    ) -> fmt::Result {
+        let padding = self.width.saturating_sub(width);
+        let (before, after) = match self.align {
+            Align::Left => (0, padding),
+            Align::Right => (padding, 0),
+            Align::Center => (padding / 2, padding - padding / 2),
+        };
+        (0..before).try_for_each(|_| out.write_char(' '))?;
+        parts.iter().try_for_each(|part| out.write_str(part))?;
+        (0..after).try_for_each(|_| out.write_char(' '))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_words() {
+        assert_eq!(title_case("hello world"), "Hello World");
+        assert_eq!(title_case("  hello\tworld "), "  Hello\tWorld ");
+        assert_eq!(title_case(""), "");
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(title_case("o'neill"), "O'Neill");
+        assert_eq!(title_case("d\u{2019}angelo"), "D\u{2019}Angelo");
+        assert_eq!(title_case("it's mcdonald"), "It's Mcdonald");
+        assert_eq!(title_case("McDonald"), "McDonald");
+        assert_eq!(title_case("jean-luc"), "Jean-Luc");
+        assert_eq!(title_case("(ana)"), "(Ana)");
+        assert_eq!(title_case("3rd"), "3rd");
+    }
+
+    #[test]
+    fn test_particles() {
+        assert_eq!(title_case("hello van der berg"), "Hello van der Berg");
+        assert_eq!(title_case("van der berg"), "Van der Berg");
+        assert_eq!(title_case("ludwig van beethoven"), "Ludwig van Beethoven");
+        let custom = TitleCase::new().particles(["bo"]);
+        assert_eq!(custom.apply("ana bo cy"), "Ana bo Cy");
+    }
+
+    #[test]
+    fn test_graphemes() {
+        // decomposed É
+        assert_eq!(title_case("e\u{301}mile"), "E\u{301}mile");
+        assert_eq!(
+            title_case("\u{1F44B}\u{1F3FD} ana"),
+            "\u{1F44B}\u{1F3FD} Ana"
+        );
+    }
+
+    #[test]
+    fn test_titlecase_mappings() {
+        assert_eq!(title_case("ǆuro"), "ǅuro");
+        assert_eq!(title_case("ßa"), "Ssa");
+    }
+
+    #[test]
+    fn test_locale_rules() {
+        let turkish = TitleCase::for_locale(&Locale::parse("tr").unwrap());
+        assert_eq!(turkish.apply("ismail ılgaz"), "İsmail Ilgaz");
+        assert_eq!(title_case("ismail"), "Ismail");
+
+        let dutch = TitleCase::for_locale(&Locale::parse("nl-BE").unwrap());
+        assert_eq!(dutch.apply("ijsselmeer"), "IJsselmeer");
+        assert_eq!(dutch.apply("inge"), "Inge");
+        assert_eq!(title_case("ijsselmeer"), "Ijsselmeer");
+    }
+
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width(""), 0);
+        assert_eq!(display_width("hello ana"), 9);
+        assert_eq!(display_width("안녕 지민"), 9);
+        // family: man, ZWJ, woman, ZWJ, girl
+        assert_eq!(
+            display_width("\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}"),
+            2
+        );
+        assert_eq!(display_width("\u{1F1E7}\u{1F1F7}"), 2);
+        assert_eq!(display_width("\u{2068}محمد\u{2069}"), 4);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let column = Column::new(5);
+        assert_eq!(column.truncate("hello"), "hello");
+        assert_eq!(column.truncate("hello!"), "hell…");
+        // never half of a wide character
+        assert_eq!(column.truncate("李小龙李小龙"), "李小…");
+        assert_eq!(
+            column.truncate("e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}"),
+            "e\u{301}e\u{301}e\u{301}e\u{301}…"
+        );
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        assert_eq!(
+            column.truncate(&family.repeat(3)),
+            format!("{family}{family}…")
+        );
+        assert_eq!(column.clone().ellipsis("").truncate("hello!"), "hello");
+        assert_eq!(Column::new(2).ellipsis("...").truncate("hello"), "he");
+        assert_eq!(Column::new(0).truncate("hello"), "");
+    }
+
+    #[test]
+    fn test_pad() {
+        assert_eq!(Column::new(6).pad("李小"), "李小  ");
+        assert_eq!(Column::new(6).align(Align::Right).pad("ana"), "   ana");
+        assert_eq!(Column::new(6).align(Align::Center).pad("ana"), " ana  ");
+        assert_eq!(Column::new(2).pad("ana"), "ana");
+        // a wide character that doesn't fit leaves a column to pad
+        let fitted = Column::new(4).ellipsis("").fit("李小龙");
+        assert_eq!(fitted, "李小");
+        assert_eq!(Column::new(5).ellipsis("").fit("李小龙"), "李小 ");
+        assert_eq!(display_width(&Column::new(7).fit("hello 李小龙")), 7);
+    }
+}
@@ -0,0 +1,414 @@
+//! Named greeting styles.
+//!
+//! A [`Greeter`] turns a locale and a name into a finished greeting. The
+//! built-in styles cover what each binding used to hard-code for itself, and a
+//! [`StyleRegistry`] lets callers pick one by name or add their own.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use crate::bidi::PDI;
+use crate::format::{CaseRules, TitleCase};
+use crate::{catalog, AddressForm, Addressed, Formality, Greeting, Locale, Template};
+
+/// A style of greeting.
+///
+/// ```
+/// use std::fmt::{self, Write};
+/// use hello_rs::{Greeter, Locale, StyleRegistry};
+///
+/// struct Pirate;
+///
+/// impl Greeter for Pirate {
+///     fn write_greeting(&self, out: &mut dyn Write, _: &Locale, name: &str) -> fmt::Result {
+///         write!(out, "ahoy {name}")
+///     }
+/// }
+///
+/// let mut styles = StyleRegistry::default();
+/// styles.register("pirate", Pirate);
+/// assert_eq!(styles.greet("pirate", &Locale::default(), "ana").unwrap(), "ahoy ana");
+/// ```
+pub trait Greeter {
+    /// Write the greeting for `name` in `locale` into `out`
+    fn write_greeting(&self, out: &mut dyn Write, locale: &Locale, name: &str) -> fmt::Result;
+
+    /// The greeting for `name` in `locale`
+    fn greet(&self, locale: &Locale, name: &str) -> String {
+        let mut out = String::new();
+        self.write_greeting(&mut out, locale, name)
+            .expect("writing to a String cannot fail");
+        out
+    }
+}
+
+/// "hello ana", exactly like [`hello_in`](crate::hello_in).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlainGreeter;
+
+impl Greeter for PlainGreeter {
+    fn write_greeting(&self, out: &mut dyn Write, locale: &Locale, name: &str) -> fmt::Result {
+        out.write_str(Formality::Neutral.salutation(locale))?;
+        out.write_char(' ')?;
+        out.write_str(name)
+    }
+}
+
+/// "Hello Ana!": whitespace collapsed, title-cased and exclaimed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FancyGreeter;
+
+impl Greeter for FancyGreeter {
+    fn write_greeting(&self, out: &mut dyn Write, locale: &Locale, name: &str) -> fmt::Result {
+        let rules = CaseRules::for_locale(locale);
+        let title_case = TitleCase::new();
+        let mut collapsed = CollapseWhitespace::new(out);
+        title_case.write_words(
+            rules,
+            Formality::Neutral.salutation(locale),
+            true,
+            &mut collapsed,
+        )?;
+        collapsed.write_char(' ')?;
+        title_case.write_words(rules, name, false, &mut collapsed)?;
+        out.write_char('!')
+    }
+}
+
+/// "Good Day, Ana.": the formal salutation, title-cased.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FormalGreeter;
+
+impl Greeter for FormalGreeter {
+    fn write_greeting(&self, out: &mut dyn Write, locale: &Locale, name: &str) -> fmt::Result {
+        let rules = CaseRules::for_locale(locale);
+        let title_case = TitleCase::new();
+        title_case.write_words(rules, Formality::Formal.salutation(locale), true, out)?;
+        out.write_str(", ")?;
+        title_case.write_words(rules, name, false, out)?;
+        // "Jr." already ends the sentence, even inside a bidi isolate
+        if name.trim_end_matches(PDI).ends_with('.') {
+            return Ok(());
+        }
+        out.write_char('.')
+    }
+}
+
+/// "HELLO ANA!"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ShoutGreeter;
+
+impl Greeter for ShoutGreeter {
+    fn write_greeting(&self, out: &mut dyn Write, locale: &Locale, name: &str) -> fmt::Result {
+        let rules = CaseRules::for_locale(locale);
+        let salutation = Formality::Neutral.salutation(locale);
+        for c in salutation.chars().chain([' ']).chain(name.chars()) {
+            rules.write_upper(c, out)?;
+        }
+        out.write_char('!')
+    }
+}
+
+/// "hey there ana": a salutation picked from the locale's variants.
+///
+/// The pick is deterministic: it depends only on the seed, or, without one,
+/// on a hash of the name's UTF-8 bytes, so the same input gives the same
+/// greeting on every platform and in every binding.
+///
+/// ```
+/// use hello_rs::{Greeter, Locale, VariedGreeter};
+///
+/// let en = Locale::default();
+/// let by_name = VariedGreeter::new();
+/// assert_eq!(by_name.greet(&en, "ana"), by_name.greet(&en, "ana"));
+///
+/// let seeded = VariedGreeter::with_seed(7);
+/// assert_eq!(seeded.greet(&en, "ana"), "greetings ana");
+/// assert_eq!(seeded.greet(&en, "bo"), "greetings bo");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VariedGreeter {
+    seed: Option<u64>,
+}
+
+impl VariedGreeter {
+    /// Pick the salutation from a hash of the name
+    pub const fn new() -> Self {
+        VariedGreeter { seed: None }
+    }
+
+    /// Pick the salutation from `seed`, whatever the name
+    pub const fn with_seed(seed: u64) -> Self {
+        VariedGreeter { seed: Some(seed) }
+    }
+
+    /// The salutation this greeter uses for `name` in `locale`
+    pub fn salutation(&self, locale: &Locale, name: &str) -> &'static str {
+        let variants = catalog::message(locale, "hello.variants");
+        let seed = self.seed.unwrap_or_else(|| fnv1a(name.as_bytes()));
+        let count = variants.split('|').count() as u64;
+        let index = (splitmix64(seed) % count) as usize;
+        variants.split('|').nth(index).unwrap_or(variants)
+    }
+}
+
+impl Greeter for VariedGreeter {
+    fn write_greeting(&self, out: &mut dyn Write, locale: &Locale, name: &str) -> fmt::Result {
+        out.write_str(self.salutation(locale, name))?;
+        out.write_char(' ')?;
+        out.write_str(name)
+    }
+}
+
+/// 64-bit FNV-1a, which is simple enough to give the same answer everywhere
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// The SplitMix64 finalizer, so that nearby seeds pick unrelated variants
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// A template is a style too: `{salutation}` comes from the locale and
+/// `{punct}` is empty.
+impl Greeter for Template {
+    fn write_greeting(&self, out: &mut dyn Write, locale: &Locale, name: &str) -> fmt::Result {
+        let greeting = Greeting::builder(name).locale(locale.clone()).build();
+        self.write(&greeting, out)
+    }
+}
+
+/// The error returned when a [`StyleRegistry`] has no style by that name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownStyle {
+    name: String,
+}
+
+impl UnknownStyle {
+    /// The style that was asked for
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for UnknownStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown greeting style: {:?}", self.name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownStyle {}
+
+/// Greeting styles looked up by name.
+///
+/// [`StyleRegistry::default`] has the built-in styles `plain`, `fancy`,
+/// `formal`, `shout` and `varied` (a [`VariedGreeter`] keyed on the name),
+/// plus `given`, `family` and `full`, which parse the
+/// name as a [`PersonName`](crate::PersonName) and address the person
+/// by given name, by honorific and family name, or by their given, middle
+/// and family names. [`StyleRegistry::new`] starts empty.
+///
+/// ```
+/// use hello_rs::{Locale, StyleRegistry, Template};
+///
+/// let mut styles = StyleRegistry::default();
+/// let en = Locale::default();
+/// assert_eq!(styles.greet("fancy", &en, "anne-marie van der berg").unwrap(), "Hello Anne-Marie van der Berg!");
+/// assert_eq!(styles.greet("formal", &en, "okafor").unwrap(), "Good Day, Okafor.");
+///
+/// styles.register("bracketed", Template::compile("[{salutation}] {name}").unwrap());
+/// assert_eq!(styles.greet("bracketed", &en, "bo").unwrap(), "[hello] bo");
+/// assert!(styles.greet("whisper", &en, "bo").is_err());
+/// ```
+pub struct StyleRegistry {
+    styles: Vec<(String, Box<dyn Greeter + Send + Sync>)>,
+}
+
+impl StyleRegistry {
+    /// A registry with no styles
+    pub const fn new() -> Self {
+        StyleRegistry { styles: Vec::new() }
+    }
+
+    /// Add `greeter` under `name`, returning the style it replaces, if any
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        greeter: impl Greeter + Send + Sync + 'static,
+    ) -> Option<Box<dyn Greeter + Send + Sync>> {
+        let name = name.into();
+        let greeter: Box<dyn Greeter + Send + Sync> = Box::new(greeter);
+        match self.styles.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, existing)) => Some(core::mem::replace(existing, greeter)),
+            None => {
+                self.styles.push((name, greeter));
+                None
+            }
+        }
+    }
+
+    /// The style registered under `name`
+    pub fn get(&self, name: &str) -> Option<&(dyn Greeter + Send + Sync)> {
+        self.styles
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, greeter)| greeter.as_ref())
+    }
+
+    /// The names of the registered styles, in the order they were added
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.styles.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Greet `name` in `locale` using the style registered as `style`
+    pub fn greet(&self, style: &str, locale: &Locale, name: &str) -> Result<String, UnknownStyle> {
+        match self.get(style) {
+            Some(greeter) => Ok(greeter.greet(locale, name)),
+            None => Err(UnknownStyle { name: style.into() }),
+        }
+    }
+}
+
+impl Default for StyleRegistry {
+    fn default() -> Self {
+        let mut registry = StyleRegistry::new();
+        registry.register("plain", PlainGreeter);
+        registry.register("fancy", FancyGreeter);
+        registry.register("formal", FormalGreeter);
+        registry.register("shout", ShoutGreeter);
+        registry.register("varied", VariedGreeter::new());
+        registry.register("given", Addressed::new(AddressForm::Given, FancyGreeter));
+        registry.register("family", Addressed::new(AddressForm::Family, FormalGreeter));
+        registry.register("full", Addressed::new(AddressForm::Full, FormalGreeter));
+        registry
+    }
+}
+
+impl fmt::Debug for StyleRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.names()).finish()
+    }
+}
+
+/// Collapses runs of whitespace into a single space and drops leading and
+/// trailing whitespace, as text streams through.
+struct CollapseWhitespace<'a, W: Write + ?Sized> {
+    inner: &'a mut W,
+    started: bool,
+    pending_space: bool,
+}
+
+impl<'a, W: Write + ?Sized> CollapseWhitespace<'a, W> {
+    fn new(inner: &'a mut W) -> Self {
+        CollapseWhitespace {
+            inner,
+            started: false,
+            pending_space: false,
+        }
+    }
+}
+
+impl<W: Write + ?Sized> Write for CollapseWhitespace<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.chars().try_for_each(|c| self.write_char(c))
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        if c.is_whitespace() {
+            self.pending_space = self.started;
+            return Ok(());
+        }
+        if self.pending_space {
+            self.inner.write_char(' ')?;
+            self.pending_space = false;
+        }
+        self.started = true;
+        self.inner.write_char(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_styles() {
+        let styles = StyleRegistry::default();
+        let greet = |style, tag, name| {
+            styles
+                .greet(style, &Locale::parse(tag).unwrap(), name)
+                .unwrap()
+        };
+        assert_eq!(greet("plain", "en", "ana"), crate::hello("ana"));
+        assert_eq!(greet("plain", "es", "ana"), "hola ana");
+        assert_eq!(greet("fancy", "en", "  rust   wasm "), "Hello Rust Wasm!");
+        assert_eq!(greet("fancy", "en", ""), "Hello!");
+        assert_eq!(greet("fancy", "en", "van gogh"), "Hello van Gogh!");
+        assert_eq!(greet("fancy", "tr", "ismail"), "Merhaba İsmail!");
+        assert_eq!(greet("formal", "de", "anna"), "Guten Tag, Anna.");
+        assert_eq!(greet("shout", "en", "ana"), "HELLO ANA!");
+        assert_eq!(
+            styles.names().collect::<Vec<_>>(),
+            ["plain", "fancy", "formal", "shout", "varied", "given", "family", "full"]
+        );
+        assert_eq!(greet("given", "en", "Dr. Jane Public"), "Hello Jane!");
+        assert_eq!(
+            greet("family", "en", "Dr. Jane Public"),
+            "Good Day, Dr. Public."
+        );
+        assert_eq!(
+            greet("full", "en", "dr. jane q. public jr."),
+            "Good Day, Jane Q. Public."
+        );
+    }
+
+    #[test]
+    fn test_varied() {
+        let en = Locale::default();
+        // Pinned so a change to the hash or the catalog order shows up here,
+        // not as a greeting that differs between platforms
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        let by_name = VariedGreeter::new();
+        assert_eq!(by_name.greet(&en, "ana"), "greetings ana");
+        assert_eq!(by_name.greet(&en, "bo"), "hello bo");
+        assert_eq!(VariedGreeter::with_seed(0).greet(&en, "ana"), "hi ana");
+
+        let picked: Vec<_> = (0..50)
+            .map(|seed| VariedGreeter::with_seed(seed).salutation(&en, ""))
+            .collect();
+        for variant in catalog::message(&en, "hello.variants").split('|') {
+            assert!(picked.contains(&variant), "{variant:?} is never picked");
+        }
+        let de = Locale::parse("de-AT").unwrap();
+        let salutation = by_name.salutation(&de, "ana");
+        assert!(catalog::message(&de, "hello.variants")
+            .split('|')
+            .any(|variant| variant == salutation));
+    }
+
+    #[test]
+    fn test_register() {
+        let mut styles = StyleRegistry::new();
+        assert!(styles.get("plain").is_none());
+        assert!(styles.register("plain", PlainGreeter).is_none());
+        let replaced = styles.register("plain", ShoutGreeter).unwrap();
+        assert_eq!(replaced.greet(&Locale::default(), "bo"), "hello bo");
+        assert_eq!(
+            styles.greet("plain", &Locale::default(), "bo"),
+            Ok("HELLO BO!".into())
+        );
+        let error = styles.greet("loud", &Locale::default(), "bo").unwrap_err();
+        assert_eq!(error.name(), "loud");
+        assert_eq!(error.to_string(), r#"unknown greeting style: "loud""#);
+    }
+}
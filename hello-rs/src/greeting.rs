@@ -0,0 +1,1080 @@
+//! A structured greeting that is assembled with a builder and rendered lazily
+//! through [`Display`](fmt::Display).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+use crate::format::{CaseRules, TitleCase};
+use crate::{calendar, catalog, AddressForm, BidiIsolation, Locale, PersonName, ZonedTime};
+
+/// How formal a greeting should sound.
+///
+/// Besides the salutation, the formality decides how a [`PersonName`] is
+/// addressed and whether T–V languages use the familiar or the polite "you":
+///
+/// | Formality    | Example                           | Addressed as           | "you" |
+/// |--------------|-----------------------------------|------------------------|-------|
+/// | `Intimate`   | "hiya Chidi"                      | given name             | du    |
+/// | `Casual`     | "hey Chidi"                       | given name             | du    |
+/// | `Neutral`    | "hello Chidi Okafor"              | full name              | Sie   |
+/// | `Formal`     | "good day, Dr. Okafor"            | honorific and family   | Sie   |
+/// | `Ceremonial` | "greetings, Dr. Chidi Okafor PhD" | every part of the name | Sie   |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum Formality {
+    /// Between close friends and family, e.g. "hiya world"
+    Intimate,
+    /// Relaxed, e.g. "hey world"
+    Casual,
+    /// The plain form produced by [`hello`](crate::hello), e.g. "hello world"
+    #[default]
+    Neutral,
+    /// Polite, e.g. "good day, world"
+    Formal,
+    /// For official occasions, e.g. "greetings, world"
+    Ceremonial,
+}
+
+impl Formality {
+    /// Every level, from least to most formal
+    pub const ALL: [Formality; 5] = [
+        Formality::Intimate,
+        Formality::Casual,
+        Formality::Neutral,
+        Formality::Formal,
+        Formality::Ceremonial,
+    ];
+
+    /// The lowercase name of the level, e.g. "formal"
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Formality::Intimate => "intimate",
+            Formality::Casual => "casual",
+            Formality::Neutral => "neutral",
+            Formality::Formal => "formal",
+            Formality::Ceremonial => "ceremonial",
+        }
+    }
+
+    /// Whether T–V languages use the familiar or the polite "you"
+    pub fn tv_form(self) -> TvForm {
+        match self {
+            Formality::Intimate | Formality::Casual => TvForm::Familiar,
+            Formality::Neutral | Formality::Formal | Formality::Ceremonial => TvForm::Polite,
+        }
+    }
+
+    /// How a [`PersonName`] is addressed at this level
+    ///
+    /// ```
+    /// use hello_rs::{Formality, Locale, PersonName};
+    ///
+    /// let name = PersonName::parse("Dr. Chidi Okafor", &Locale::default());
+    /// assert_eq!(name.address(Formality::Casual.address_form()), "Chidi");
+    /// assert_eq!(name.address(Formality::Formal.address_form()), "Dr. Okafor");
+    /// ```
+    pub fn address_form(self) -> AddressForm {
+        match self {
+            Formality::Intimate | Formality::Casual => AddressForm::Given,
+            Formality::Neutral => AddressForm::Full,
+            Formality::Formal => AddressForm::Family,
+            Formality::Ceremonial => AddressForm::Formal,
+        }
+    }
+
+    /// The salutation used in `locale` when none has been set explicitly
+    ///
+    /// ```
+    /// use hello_rs::{Formality, Locale};
+    ///
+    /// let de = Locale::parse("de").unwrap();
+    /// assert_eq!(Formality::Formal.salutation(&de), "guten Tag");
+    /// ```
+    pub fn salutation(self, locale: &Locale) -> &'static str {
+        let key = match self {
+            Formality::Intimate => "hello.intimate",
+            Formality::Casual => "hello.casual",
+            Formality::Neutral => "hello",
+            Formality::Formal => "hello.formal",
+            Formality::Ceremonial => "hello.ceremonial",
+        };
+        catalog::message(locale, key)
+    }
+
+    /// The text placed between the salutation and the recipient
+    fn separator(self) -> &'static str {
+        match self {
+            Formality::Intimate | Formality::Casual | Formality::Neutral => " ",
+            Formality::Formal | Formality::Ceremonial => ", ",
+        }
+    }
+
+    /// Whether a greeting with a time uses a time-of-day salutation; relaxed
+    /// greetings keep their own
+    fn follows_time(self) -> bool {
+        self >= Formality::Neutral
+    }
+}
+
+impl fmt::Display for Formality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Formality {
+    type Err = FormalityError;
+
+    /// Parse a name produced by [`Formality::as_str`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Formality::ALL
+            .into_iter()
+            .find(|formality| formality.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| FormalityError { name: s.into() })
+    }
+}
+
+/// The error returned when parsing a [`Formality`] from an unknown name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormalityError {
+    name: String,
+}
+
+impl fmt::Display for FormalityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown formality: {:?}", self.name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FormalityError {}
+
+/// Which "you" a language with a T–V distinction uses, e.g. German
+/// "du"/"Sie" or French "tu"/"vous".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TvForm {
+    /// The familiar form, e.g. "du"
+    Familiar,
+    /// The polite form, e.g. "Sie"
+    Polite,
+}
+
+impl TvForm {
+    /// The second-person pronoun in `locale`
+    ///
+    /// ```
+    /// use hello_rs::{Locale, TvForm};
+    ///
+    /// let fr = Locale::parse("fr").unwrap();
+    /// assert_eq!(TvForm::Familiar.pronoun(&fr), "tu");
+    /// assert_eq!(TvForm::Polite.pronoun(&fr), "vous");
+    /// ```
+    pub fn pronoun(self, locale: &Locale) -> &'static str {
+        match self {
+            TvForm::Familiar => catalog::message(locale, "you.familiar"),
+            TvForm::Polite => catalog::message(locale, "you.polite"),
+        }
+    }
+
+    /// "How are you?" in `locale`, addressing someone in this form
+    pub fn how_are_you(self, locale: &Locale) -> &'static str {
+        match self {
+            TvForm::Familiar => catalog::message(locale, "how_are_you.familiar"),
+            TvForm::Polite => catalog::message(locale, "how_are_you.polite"),
+        }
+    }
+}
+
+/// The grammatical gender a greeting agrees with, where the language has one.
+///
+/// Only words that change with the recipient are affected, such as Spanish
+/// "bienvenido"/"bienvenida". The default is a gender-neutral form
+/// ("bienvenide"), so nothing has to be assumed about the recipient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Gender {
+    /// e.g. "bienvenida"
+    Feminine,
+    /// e.g. "bienvenido"
+    Masculine,
+    /// e.g. "bienvenide"
+    #[default]
+    Neutral,
+}
+
+impl Gender {
+    /// Every gender, with [`Gender::Neutral`] last
+    pub const ALL: [Gender; 3] = [Gender::Feminine, Gender::Masculine, Gender::Neutral];
+
+    /// The lowercase name of the gender, e.g. "feminine"
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Gender::Feminine => "feminine",
+            Gender::Masculine => "masculine",
+            Gender::Neutral => "neutral",
+        }
+    }
+
+    /// "Welcome" in `locale`, agreeing with this gender
+    ///
+    /// ```
+    /// use hello_rs::{Gender, Locale};
+    ///
+    /// let es = Locale::parse("es").unwrap();
+    /// assert_eq!(Gender::Feminine.welcome(&es), "bienvenida");
+    /// assert_eq!(Gender::Neutral.welcome(&es), "bienvenide");
+    /// ```
+    pub fn welcome(self, locale: &Locale) -> &'static str {
+        match self {
+            Gender::Feminine => catalog::message(locale, "welcome.feminine"),
+            Gender::Masculine => catalog::message(locale, "welcome.masculine"),
+            Gender::Neutral => catalog::message(locale, "welcome.neutral"),
+        }
+    }
+}
+
+impl fmt::Display for Gender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Gender {
+    type Err = GenderError;
+
+    /// Parse a name produced by [`Gender::as_str`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Gender::ALL
+            .into_iter()
+            .find(|gender| gender.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| GenderError { name: s.into() })
+    }
+}
+
+/// The error returned when parsing a [`Gender`] from an unknown name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenderError {
+    name: String,
+}
+
+impl fmt::Display for GenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown gender: {:?}", self.name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GenderError {}
+
+/// How the rendered greeting should be capitalized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Capitalization {
+    /// Leave the text exactly as given
+    #[default]
+    AsIs,
+    /// "hello world"
+    Lower,
+    /// "HELLO WORLD"
+    Upper,
+    /// "Hello world"
+    Sentence,
+    /// "Hello World", following the rules of [`TitleCase`]
+    Title,
+}
+
+impl Capitalization {
+    /// Every capitalization
+    pub const ALL: [Capitalization; 5] = [
+        Capitalization::AsIs,
+        Capitalization::Lower,
+        Capitalization::Upper,
+        Capitalization::Sentence,
+        Capitalization::Title,
+    ];
+
+    /// The snake_case name of the capitalization, e.g. "as_is"
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Capitalization::AsIs => "as_is",
+            Capitalization::Lower => "lower",
+            Capitalization::Upper => "upper",
+            Capitalization::Sentence => "sentence",
+            Capitalization::Title => "title",
+        }
+    }
+}
+
+impl fmt::Display for Capitalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Capitalization {
+    type Err = CapitalizationError;
+
+    /// Parse a name produced by [`Capitalization::as_str`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Capitalization::ALL
+            .into_iter()
+            .find(|capitalization| capitalization.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| CapitalizationError { name: s.into() })
+    }
+}
+
+/// The error returned when parsing a [`Capitalization`] from an unknown name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapitalizationError {
+    name: String,
+}
+
+impl fmt::Display for CapitalizationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown capitalization: {:?}", self.name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapitalizationError {}
+
+/// A greeting whose parts can be inspected before it is rendered.
+///
+/// The default greeting renders exactly like [`hello`](crate::hello):
+///
+/// ```
+/// use hello_rs::{Capitalization, Formality, Greeting};
+///
+/// assert_eq!(Greeting::new("world").to_string(), "hello world");
+///
+/// let greeting = Greeting::builder("okafor")
+///     .formality(Formality::Formal)
+///     .capitalization(Capitalization::Title)
+///     .punctuation(".")
+///     .build();
+/// assert_eq!(greeting.to_string(), "Good Day, Okafor.");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct Greeting {
+    #[cfg_attr(feature = "serde", serde(default))]
+    salutation: Option<String>,
+    recipient: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    punctuation: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    formality: Formality,
+    #[cfg_attr(feature = "serde", serde(default))]
+    capitalization: Capitalization,
+    #[cfg_attr(feature = "serde", serde(default))]
+    locale: Locale,
+    #[cfg_attr(feature = "serde", serde(default))]
+    time: Option<ZonedTime>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    how_are_you: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    gender: Gender,
+    #[cfg_attr(feature = "serde", serde(default))]
+    welcome: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    bidi: Option<BidiIsolation>,
+}
+
+impl Greeting {
+    /// Create a plain greeting for `recipient`
+    pub fn new(recipient: impl Into<String>) -> Self {
+        Greeting {
+            salutation: None,
+            recipient: recipient.into(),
+            punctuation: String::new(),
+            formality: Formality::default(),
+            capitalization: Capitalization::default(),
+            locale: Locale::default(),
+            time: None,
+            how_are_you: false,
+            gender: Gender::default(),
+            welcome: false,
+            bidi: None,
+        }
+    }
+
+    /// Start building a greeting for `recipient`
+    pub fn builder(recipient: impl Into<String>) -> GreetingBuilder {
+        GreetingBuilder {
+            greeting: Greeting::new(recipient),
+            person: None,
+        }
+    }
+
+    /// Start building a greeting for `person`, who is addressed the way the
+    /// greeting's [`Formality`] calls for
+    ///
+    /// ```
+    /// use hello_rs::{Formality, Greeting, Locale, PersonName};
+    ///
+    /// let name = PersonName::parse("Dr. Chidi Okafor", &Locale::default());
+    /// let greeting = Greeting::for_person(name.clone()).formality(Formality::Formal).build();
+    /// assert_eq!(greeting.to_string(), "good day, Dr. Okafor");
+    /// let greeting = Greeting::for_person(name).formality(Formality::Casual).build();
+    /// assert_eq!(greeting.to_string(), "hey Chidi");
+    /// ```
+    pub fn for_person(person: PersonName) -> GreetingBuilder {
+        GreetingBuilder {
+            greeting: Greeting::new(String::new()),
+            person: Some(person),
+        }
+    }
+
+    /// The salutation, falling back to the one the catalog has for the
+    /// locale and formality, or for the time of day when a time is set and
+    /// the greeting isn't casual or intimate
+    ///
+    /// A welcome uses the catalog's "welcome" for the recipient's gender
+    /// instead.
+    pub fn salutation(&self) -> &str {
+        if let Some(salutation) = &self.salutation {
+            return salutation;
+        }
+        if self.welcome {
+            return self.gender.welcome(&self.locale);
+        }
+        match self.time {
+            Some(time) if self.formality.follows_time() => calendar::salutation(time, &self.locale),
+            _ => self.formality.salutation(&self.locale),
+        }
+    }
+
+    /// Who is being greeted
+    pub fn recipient(&self) -> &str {
+        &self.recipient
+    }
+
+    /// Trailing punctuation, empty by default
+    pub fn punctuation(&self) -> &str {
+        &self.punctuation
+    }
+
+    /// How formal the greeting is
+    pub fn formality(&self) -> Formality {
+        self.formality
+    }
+
+    /// How the greeting is capitalized when rendered
+    pub fn capitalization(&self) -> Capitalization {
+        self.capitalization
+    }
+
+    /// The locale used to translate the salutation
+    pub fn locale(&self) -> &Locale {
+        &self.locale
+    }
+
+    /// The time the greeting is for, if it depends on one
+    pub fn time(&self) -> Option<ZonedTime> {
+        self.time
+    }
+
+    /// The grammatical gender of the recipient
+    pub fn gender(&self) -> Gender {
+        self.gender
+    }
+
+    /// How the recipient is isolated from the rest of the greeting, if it is
+    pub fn bidi(&self) -> Option<BidiIsolation> {
+        self.bidi
+    }
+
+    /// The "how are you?" that follows the greeting, in the familiar or
+    /// polite form its formality calls for, if one was asked for
+    pub fn how_are_you(&self) -> Option<&'static str> {
+        self.how_are_you
+            .then(|| self.formality.tv_form().how_are_you(&self.locale))
+    }
+}
+
+impl Greeting {
+    /// Write the greeting one part at a time, already capitalized, so a
+    /// renderer can escape or mark up each part on its own
+    ///
+    /// Each part is passed whole, and joining them gives exactly the
+    /// [`Display`](fmt::Display) text.
+    ///
+    /// ```
+    /// use hello_rs::{Capitalization, Greeting, Part};
+    ///
+    /// let greeting = Greeting::builder("ana")
+    ///     .capitalization(Capitalization::Title)
+    ///     .punctuation("!")
+    ///     .build();
+    /// let mut parts = Vec::new();
+    /// greeting.write_parts(|part, text| {
+    ///     parts.push((part, text.to_string()));
+    ///     Ok(())
+    /// }).unwrap();
+    /// assert_eq!(parts[2], (Part::Recipient, "Ana".to_string()));
+    /// ```
+    pub fn write_parts<F>(&self, mut emit: F) -> fmt::Result
+    where
+        F: FnMut(Part, &str) -> fmt::Result,
+    {
+        let mut parts: Vec<(Part, String)> = Vec::new();
+        self.write_raw_parts(&mut |part, text| {
+            match parts.last_mut() {
+                Some((last, buffer)) if *last == part => buffer.push_str(text),
+                _ => parts.push((part, text.into())),
+            }
+            Ok(())
+        })?;
+
+        let rules = CaseRules::for_locale(&self.locale);
+        let title_case = (self.capitalization == Capitalization::Title)
+            .then(|| TitleCase::for_locale(&self.locale));
+        let mut first_word = true;
+        let mut seen_letter = false;
+        let mut cased = String::new();
+        for (part, text) in &parts {
+            cased.clear();
+            match &title_case {
+                Some(title_case) => title_case.write_words(rules, text, first_word, &mut cased)?,
+                None => {
+                    let mut case = CaseWriter::new(&mut cased, self.capitalization, rules);
+                    case.seen_letter = seen_letter;
+                    case.write_str(text)?;
+                }
+            }
+            first_word &= text.trim().is_empty();
+            seen_letter |= text.contains(char::is_alphabetic);
+            emit(*part, &cased)?;
+        }
+        Ok(())
+    }
+
+    /// Feed each part to `emit` before capitalization, possibly in several
+    /// pieces
+    fn write_raw_parts(&self, emit: &mut dyn FnMut(Part, &str) -> fmt::Result) -> fmt::Result {
+        let mut out = PartWriter {
+            part: Part::Salutation,
+            emit,
+        };
+        out.write_str(self.salutation())?;
+        out.part = Part::Separator;
+        out.write_str(self.formality.separator())?;
+        out.part = Part::Recipient;
+        match &self.bidi {
+            Some(bidi) => bidi.write(&self.recipient, &mut out)?,
+            None => out.write_str(&self.recipient)?,
+        }
+        out.part = Part::Punctuation;
+        out.write_str(&self.punctuation)?;
+        let Some(question) = self.how_are_you() else {
+            return Ok(());
+        };
+        out.part = Part::Separator;
+        if self.punctuation.is_empty() {
+            out.write_str(", ")?;
+            out.part = Part::Question;
+            return out.write_str(question);
+        }
+        out.write_char(' ')?;
+        // A new sentence starts after the punctuation, possibly behind a "¿"
+        out.part = Part::Question;
+        let start = question.find(char::is_alphabetic).unwrap_or(0);
+        let (lead, rest) = question.split_at(start);
+        out.write_str(lead)?;
+        let mut chars = rest.chars();
+        if let Some(first) = chars.next() {
+            CaseRules::for_locale(&self.locale).write_upper(first, &mut out)?;
+        }
+        out.write_str(chars.as_str())
+    }
+}
+
+impl fmt::Display for Greeting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.capitalization == Capitalization::Title {
+            // Particles are recognized a whole word at a time, so title-casing
+            // can't be streamed like the other capitalizations
+            return self.write_parts(|_, text| f.write_str(text));
+        }
+        let rules = CaseRules::for_locale(&self.locale);
+        let mut case = CaseWriter::new(f, self.capitalization, rules);
+        self.write_raw_parts(&mut |_, text| case.write_str(text))
+    }
+}
+
+/// The parts of a rendered greeting, as passed to [`Greeting::write_parts`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    /// e.g. "good day"
+    Salutation,
+    /// The text between the other parts, e.g. ", "
+    Separator,
+    /// Who is being greeted
+    Recipient,
+    /// Trailing punctuation such as "!"
+    Punctuation,
+    /// The "how are you?" asked after the greeting
+    Question,
+}
+
+/// Passes what is written to a callback along with the part it belongs to.
+struct PartWriter<'a, F: ?Sized> {
+    part: Part,
+    emit: &'a mut F,
+}
+
+impl<F: FnMut(Part, &str) -> fmt::Result + ?Sized> Write for PartWriter<'_, F> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.is_empty() {
+            return Ok(());
+        }
+        (self.emit)(self.part, s)
+    }
+}
+
+/// Builder for [`Greeting`], created with [`Greeting::builder`].
+#[derive(Debug, Clone)]
+pub struct GreetingBuilder {
+    greeting: Greeting,
+    person: Option<PersonName>,
+}
+
+impl GreetingBuilder {
+    /// Replace the salutation, e.g. "welcome"
+    pub fn salutation(mut self, salutation: impl Into<String>) -> Self {
+        self.greeting.salutation = Some(salutation.into());
+        self
+    }
+
+    /// Replace the recipient
+    pub fn recipient(mut self, recipient: impl Into<String>) -> Self {
+        self.greeting.recipient = recipient.into();
+        self
+    }
+
+    /// Append punctuation after the recipient, e.g. "!"
+    pub fn punctuation(mut self, punctuation: impl Into<String>) -> Self {
+        self.greeting.punctuation = punctuation.into();
+        self
+    }
+
+    /// Choose how formal the greeting is
+    pub fn formality(mut self, formality: Formality) -> Self {
+        self.greeting.formality = formality;
+        self
+    }
+
+    /// Choose how the greeting is capitalized
+    pub fn capitalization(mut self, capitalization: Capitalization) -> Self {
+        self.greeting.capitalization = capitalization;
+        self
+    }
+
+    /// Choose the language of the salutation
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.greeting.locale = locale;
+        self
+    }
+
+    /// Pick the salutation for the time of day, or for a holiday, at `time`
+    pub fn time(mut self, time: ZonedTime) -> Self {
+        self.greeting.time = Some(time);
+        self
+    }
+
+    /// Set the recipient's grammatical gender, which is neutral unless given
+    pub fn gender(mut self, gender: Gender) -> Self {
+        self.greeting.gender = gender;
+        self
+    }
+
+    /// Welcome the recipient instead of greeting them, e.g. "bienvenida Ana"
+    pub fn welcome(mut self, welcome: bool) -> Self {
+        self.greeting.welcome = welcome;
+        self
+    }
+
+    /// Protect the rest of the greeting from a right-to-left recipient, see
+    /// [`BidiIsolation`]
+    pub fn bidi(mut self, bidi: BidiIsolation) -> Self {
+        self.greeting.bidi = Some(bidi);
+        self
+    }
+
+    /// Follow the greeting with "how are you?", using the familiar or polite
+    /// "you" to match the formality
+    pub fn how_are_you(mut self, ask: bool) -> Self {
+        self.greeting.how_are_you = ask;
+        self
+    }
+
+    /// Finish building
+    pub fn build(self) -> Greeting {
+        let mut greeting = self.greeting;
+        if let Some(person) = self.person {
+            greeting.recipient = person.address(greeting.formality.address_form());
+        }
+        greeting
+    }
+}
+
+/// Applies a [`Capitalization`] other than [`Capitalization::Title`] to text
+/// as it streams through, so rendering never needs an intermediate `String`.
+struct CaseWriter<'a, W: Write + ?Sized> {
+    inner: &'a mut W,
+    capitalization: Capitalization,
+    rules: CaseRules,
+    seen_letter: bool,
+}
+
+impl<'a, W: Write + ?Sized> CaseWriter<'a, W> {
+    fn new(inner: &'a mut W, capitalization: Capitalization, rules: CaseRules) -> Self {
+        CaseWriter {
+            inner,
+            capitalization,
+            rules,
+            seen_letter: false,
+        }
+    }
+}
+
+impl<W: Write + ?Sized> Write for CaseWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.capitalization == Capitalization::AsIs {
+            return self.inner.write_str(s);
+        }
+        s.chars().try_for_each(|c| self.write_char(c))
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        let result = match self.capitalization {
+            Capitalization::Lower => self.rules.write_lower(c, self.inner),
+            Capitalization::Upper => self.rules.write_upper(c, self.inner),
+            Capitalization::Sentence if c.is_alphabetic() && !self.seen_letter => {
+                self.rules.write_upper(c, self.inner)
+            }
+            _ => self.inner.write_char(c),
+        };
+        self.seen_letter |= c.is_alphabetic();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matches_hello() {
+        for name in ["claude", "world", "", "rust wasm"] {
+            assert_eq!(Greeting::new(name).to_string(), crate::hello(name));
+        }
+    }
+
+    #[test]
+    fn test_builder() {
+        let greeting = Greeting::builder("ana")
+            .salutation("welcome")
+            .punctuation("!")
+            .capitalization(Capitalization::Sentence)
+            .build();
+        assert_eq!(greeting.salutation(), "welcome");
+        assert_eq!(greeting.recipient(), "ana");
+        assert_eq!(greeting.to_string(), "Welcome ana!");
+    }
+
+    #[test]
+    fn test_formality() {
+        assert_eq!(
+            Greeting::builder("bo")
+                .formality(Formality::Casual)
+                .build()
+                .to_string(),
+            "hey bo"
+        );
+        assert_eq!(
+            Greeting::builder("bo")
+                .formality(Formality::Formal)
+                .build()
+                .to_string(),
+            "good day, bo"
+        );
+    }
+
+    #[test]
+    fn test_locale() {
+        let pt_br = Locale::parse("pt-BR").unwrap();
+        let render = |formality| {
+            Greeting::builder("ana")
+                .locale(pt_br.clone())
+                .formality(formality)
+                .build()
+                .to_string()
+        };
+        assert_eq!(render(Formality::Casual), "oi ana");
+        assert_eq!(render(Formality::Neutral), "olá ana");
+        assert_eq!(render(Formality::Formal), "cumprimentos, ana");
+    }
+
+    #[test]
+    fn test_time() {
+        // 2024-06-03T19:00:00Z
+        let evening = ZonedTime::new(1_717_441_200, crate::UtcOffset::UTC);
+        let render = |formality| {
+            Greeting::builder("bo")
+                .time(evening)
+                .formality(formality)
+                .build()
+                .to_string()
+        };
+        assert_eq!(render(Formality::Neutral), "good evening bo");
+        assert_eq!(render(Formality::Formal), "good evening, bo");
+        assert_eq!(render(Formality::Casual), "hey bo");
+    }
+
+    #[test]
+    fn test_levels() {
+        let render = |formality| {
+            Greeting::builder("bo")
+                .formality(formality)
+                .build()
+                .to_string()
+        };
+        assert_eq!(render(Formality::Intimate), "hiya bo");
+        assert_eq!(render(Formality::Ceremonial), "greetings, bo");
+        for formality in Formality::ALL {
+            assert_eq!(formality.as_str().parse(), Ok(formality));
+        }
+        assert_eq!("FORMAL".parse(), Ok(Formality::Formal));
+        let error = "posh".parse::<Formality>().unwrap_err();
+        assert_eq!(error.to_string(), r#"unknown formality: "posh""#);
+    }
+
+    #[test]
+    fn test_person() {
+        let name = PersonName::parse("Dr. Chidi Okafor PhD", &Locale::default());
+        let render = |formality| {
+            Greeting::for_person(name.clone())
+                .formality(formality)
+                .capitalization(Capitalization::Sentence)
+                .build()
+                .to_string()
+        };
+        assert_eq!(render(Formality::Intimate), "Hiya Chidi");
+        assert_eq!(render(Formality::Neutral), "Hello Chidi Okafor");
+        assert_eq!(render(Formality::Formal), "Good day, Dr. Okafor");
+        assert_eq!(
+            render(Formality::Ceremonial),
+            "Greetings, Dr. Chidi Okafor PhD"
+        );
+    }
+
+    #[test]
+    fn test_how_are_you() {
+        let de = Locale::parse("de").unwrap();
+        let render = |formality, punctuation| {
+            Greeting::builder("Anna")
+                .locale(de.clone())
+                .formality(formality)
+                .punctuation(punctuation)
+                .how_are_you(true)
+                .build()
+                .to_string()
+        };
+        assert_eq!(render(Formality::Casual, ""), "hi Anna, wie geht's dir?");
+        assert_eq!(
+            render(Formality::Formal, "!"),
+            "guten Tag, Anna! Wie geht es Ihnen?"
+        );
+        let es = Greeting::builder("Ana")
+            .locale(Locale::parse("es").unwrap())
+            .punctuation(".")
+            .how_are_you(true)
+            .build();
+        assert_eq!(es.to_string(), "hola Ana. ¿Cómo está usted?");
+        assert_eq!(Greeting::new("bo").how_are_you(), None);
+    }
+
+    #[test]
+    fn test_welcome() {
+        let welcome = |tag, gender| {
+            Greeting::builder("Sam")
+                .locale(Locale::parse(tag).unwrap())
+                .gender(gender)
+                .welcome(true)
+                .build()
+                .to_string()
+        };
+        let expected = [
+            ("en", ["welcome", "welcome", "welcome"]),
+            ("de", ["willkommen", "willkommen", "willkommen"]),
+            ("es", ["bienvenida", "bienvenido", "bienvenide"]),
+            ("fr", ["bienvenue", "bienvenu", "bienvenu·e"]),
+            ("it", ["benvenuta", "benvenuto", "benvenutə"]),
+            ("nl", ["welkom", "welkom", "welkom"]),
+            ("pt", ["bem-vinda", "bem-vindo", "bem-vinde"]),
+            ("pt-BR", ["bem-vinda", "bem-vindo", "bem-vinde"]),
+            ("tr", ["hoş geldiniz", "hoş geldiniz", "hoş geldiniz"]),
+        ];
+        for (tag, words) in expected {
+            for (gender, word) in Gender::ALL.into_iter().zip(words) {
+                assert_eq!(
+                    welcome(tag, gender),
+                    format!("{word} Sam"),
+                    "{tag} {gender}"
+                );
+            }
+        }
+        assert_eq!(
+            crate::supported_locales().count(),
+            expected.len(),
+            "every supported locale should be covered"
+        );
+    }
+
+    #[test]
+    fn test_gender_defaults_to_neutral() {
+        let greeting = Greeting::builder("Sam")
+            .locale(Locale::parse("es").unwrap())
+            .welcome(true)
+            .build();
+        assert_eq!(greeting.gender(), Gender::Neutral);
+        assert_eq!(greeting.to_string(), "bienvenide Sam");
+        // Gender only changes words that agree with the recipient
+        let hello = Greeting::builder("Sam").gender(Gender::Feminine).build();
+        assert_eq!(hello.to_string(), "hello Sam");
+    }
+
+    #[test]
+    fn test_gender_from_str() {
+        for gender in Gender::ALL {
+            assert_eq!(gender.as_str().parse(), Ok(gender));
+        }
+        assert_eq!("Feminine".parse(), Ok(Gender::Feminine));
+        let error = "other".parse::<Gender>().unwrap_err();
+        assert_eq!(error.to_string(), r#"unknown gender: "other""#);
+    }
+
+    #[test]
+    fn test_bidi() {
+        let greeting = Greeting::builder("محمد")
+            .punctuation("!")
+            .bidi(BidiIsolation::new())
+            .build();
+        assert_eq!(greeting.recipient(), "محمد");
+        assert_eq!(greeting.to_string(), "hello \u{2068}محمد\u{2069}!");
+        let greeting = Greeting::builder("Ana\u{202E}")
+            .capitalization(Capitalization::Title)
+            .bidi(BidiIsolation::new().strip_controls(true))
+            .build();
+        assert_eq!(greeting.to_string(), "Hello Ana");
+    }
+
+    #[test]
+    fn test_parts() {
+        let greeting = Greeting::for_person(PersonName::parse(
+            "ms. ana van der berg",
+            &Locale::default(),
+        ))
+        .formality(Formality::Formal)
+        .capitalization(Capitalization::Title)
+        .punctuation("!")
+        .how_are_you(true)
+        .build();
+        let mut parts = Vec::new();
+        greeting
+            .write_parts(|part, text| {
+                parts.push((part, String::from(text)));
+                Ok(())
+            })
+            .unwrap();
+        let expected = [
+            (Part::Salutation, "Good Day"),
+            (Part::Separator, ", "),
+            (Part::Recipient, "Ms. van der Berg"),
+            (Part::Punctuation, "!"),
+            (Part::Separator, " "),
+            (Part::Question, "How Are You?"),
+        ];
+        assert_eq!(
+            parts,
+            expected.map(|(part, text)| (part, String::from(text)))
+        );
+        assert_eq!(
+            greeting.to_string(),
+            "Good Day, Ms. van der Berg! How Are You?"
+        );
+
+        for capitalization in [
+            Capitalization::AsIs,
+            Capitalization::Lower,
+            Capitalization::Upper,
+            Capitalization::Sentence,
+        ] {
+            let greeting = Greeting::builder("ana")
+                .capitalization(capitalization)
+                .how_are_you(true)
+                .build();
+            let mut joined = String::new();
+            greeting
+                .write_parts(|_, text| {
+                    joined.push_str(text);
+                    Ok(())
+                })
+                .unwrap();
+            assert_eq!(joined, greeting.to_string());
+        }
+    }
+
+    #[test]
+    fn test_capitalization() {
+        let render = |capitalization| {
+            Greeting::builder("rust wasm")
+                .capitalization(capitalization)
+                .build()
+                .to_string()
+        };
+        assert_eq!(render(Capitalization::AsIs), "hello rust wasm");
+        assert_eq!(render(Capitalization::Upper), "HELLO RUST WASM");
+        assert_eq!(render(Capitalization::Sentence), "Hello rust wasm");
+        assert_eq!(render(Capitalization::Title), "Hello Rust Wasm");
+        assert_eq!(
+            Greeting::builder("o'neill van der berg")
+                .capitalization(Capitalization::Title)
+                .build()
+                .to_string(),
+            "Hello O'Neill van der Berg"
+        );
+        assert_eq!(
+            Greeting::builder("WORLD")
+                .capitalization(Capitalization::Lower)
+                .build()
+                .to_string(),
+            "hello world"
+        );
+        for capitalization in Capitalization::ALL {
+            assert_eq!(capitalization.as_str().parse(), Ok(capitalization));
+        }
+        let error = "shouty".parse::<Capitalization>().unwrap_err();
+        assert_eq!(error.to_string(), r#"unknown capitalization: "shouty""#);
+    }
+}
@@ -1,3 +1,74 @@
+//! Greetings, formatted and translated.
+//!
+//! # Features
+//!
+//! - `std` (default): implements `std::error::Error` for the error types and
+//!   provides [`SystemClock`]. Without it the crate is `#![no_std]` and only
+//!   needs `alloc`, so it can be used in firmware and bare
+//!   `wasm32-unknown-unknown` builds.
+//! - `serde`: implements `Serialize` and `Deserialize` for [`GreetingConfig`],
+//!   [`Greeting`], [`Locale`], [`Template`] and the enums they use, following
+//!   the schema documented on [`GreetingConfig`].
+//! - `contract`: provides [`contract`], golden vectors and assertion helpers
+//!   that downstream crates run in their own tests to check the greeting
+//!   format they depend on, plus a loader for the shared
+//!   `vectors/greetings.json`. Enables `serde`.
+//! - `uniffi`: derives the [UniFFI](https://mozilla.github.io/uniffi-rs/)
+//!   traits on [`Locale`], [`format::TitleCase`], [`Formality`], [`Format`],
+//!   [`Script`], [`BidiIsolation`] and the errors they return, and exports
+//!   [`hello`] and [`hello_in`], so a cdylib that links hello-rs can build
+//!   Kotlin, Swift or Python bindings on them. Enables `std`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+mod bidi;
+pub mod calendar;
+mod catalog;
+mod clock;
+mod config;
+#[cfg(feature = "contract")]
+pub mod contract;
+pub mod format;
+mod greeter;
+mod greeting;
+mod list;
+mod locale;
+mod name;
+mod render;
+mod template;
+mod unicode;
+mod validate;
+
+pub use bidi::{BidiIsolation, Direction};
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+pub use clock::{CivilDate, Clock, FixedClock, UtcOffset, ZonedTime};
+pub use config::GreetingConfig;
+pub use greeter::{
+    FancyGreeter, FormalGreeter, Greeter, PlainGreeter, ShoutGreeter, StyleRegistry, UnknownStyle,
+    VariedGreeter,
+};
+pub use greeting::{
+    Capitalization, CapitalizationError, Formality, FormalityError, Gender, GenderError, Greeting,
+    GreetingBuilder, Part, TvForm,
+};
+pub use list::ListFormat;
+pub use locale::{supported_locales, Locale, LocaleError};
+pub use name::{AddressForm, Addressed, NameOrder, PersonName};
+pub use render::{Ansi, Format, FormatError, Html, Json, Markdown, Render};
+#[doc(hidden)]
+pub use template::__check_template;
+pub use template::{Template, TemplateError};
+pub use validate::{HelloError, NamePolicy, Script};
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!("hello");
+
 /// A simple Rust library that greets someone
 ///
 /// # Examples
@@ -6,8 +77,172 @@
 /// use hello_rs::hello;
 /// assert_eq!(hello("world"), "hello world");
 /// ```
+#[cfg_attr(feature = "uniffi", uniffi::export)]
 pub fn hello(name: &str) -> String {
-    format!("hello {}", name)
+    let mut out = String::with_capacity("hello ".len() + name.len());
+    write_hello(&mut out, name).expect("writing to a String cannot fail");
+    out
+}
+
+/// Write the greeting [`hello`] returns into `out` without allocating
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// let mut csv = String::new();
+/// for name in ["ana", "bo"] {
+///     hello_rs::write_hello(&mut csv, name).unwrap();
+///     csv.push('\n');
+/// }
+/// assert_eq!(csv, "hello ana\nhello bo\n");
+/// ```
+pub fn write_hello<W: fmt::Write + ?Sized>(out: &mut W, name: &str) -> fmt::Result {
+    out.write_str("hello ")?;
+    out.write_str(name)
+}
+
+/// Write the greeting [`hello`] returns into a byte sink such as a file or
+/// socket without allocating
+///
+/// # Examples
+///
+/// ```
+/// let mut out = Vec::new();
+/// hello_rs::write_hello_io(&mut out, "world").unwrap();
+/// assert_eq!(out, b"hello world");
+/// ```
+#[cfg(feature = "std")]
+pub fn write_hello_io<W: std::io::Write + ?Sized>(out: &mut W, name: &str) -> std::io::Result<()> {
+    out.write_all(b"hello ")?;
+    out.write_all(name.as_bytes())
+}
+
+/// Greet someone into a caller-owned buffer, replacing its contents
+///
+/// Reusing one buffer across calls means greeting stops allocating once the
+/// buffer has grown to fit the longest greeting.
+///
+/// # Examples
+///
+/// ```
+/// let mut buf = String::new();
+/// for name in ["ana", "bo"] {
+///     let greeting = hello_rs::hello_into(&mut buf, name);
+///     assert!(greeting.starts_with("hello "));
+/// }
+/// assert_eq!(buf, "hello bo");
+/// ```
+pub fn hello_into<'a>(buf: &'a mut String, name: &str) -> &'a str {
+    buf.clear();
+    write_hello(buf, name).expect("writing to a String cannot fail");
+    buf
+}
+
+/// Greet someone in the given locale, falling back through
+/// [`Locale::fallback_chain`] when a translation is missing
+///
+/// # Examples
+///
+/// ```
+/// use hello_rs::{hello_in, Locale};
+/// assert_eq!(hello_in(&Locale::parse("es").unwrap(), "mundo"), "hola mundo");
+/// assert_eq!(hello_in(&Locale::parse("en-GB").unwrap(), "world"), "hello world");
+/// ```
+#[cfg_attr(feature = "uniffi", uniffi::export)]
+pub fn hello_in(locale: &Locale, name: &str) -> String {
+    Greeting::builder(name)
+        .locale(locale.clone())
+        .build()
+        .to_string()
+}
+
+/// Greet someone for the time of day, or the holiday, that `clock` reads
+///
+/// # Examples
+///
+/// ```
+/// use hello_rs::{hello_now, FixedClock, Locale, UtcOffset, ZonedTime};
+///
+/// // 2024-12-25T10:00:00Z
+/// let clock = FixedClock(ZonedTime::new(1_735_120_800, UtcOffset::UTC));
+/// let fr = Locale::parse("fr").unwrap();
+/// assert_eq!(hello_now(&clock, &fr, "Ana"), "joyeux Noël Ana");
+/// ```
+pub fn hello_now(clock: &dyn Clock, locale: &Locale, name: &str) -> String {
+    Greeting::builder(name)
+        .locale(locale.clone())
+        .time(clock.now())
+        .build()
+        .to_string()
+}
+
+/// How many names [`hello_all`] lists before summarizing the rest
+pub const DEFAULT_MAX_NAMES: usize = 10;
+
+/// Greet several people at once
+///
+/// Names are joined with [`ListFormat`], and lists longer than
+/// [`DEFAULT_MAX_NAMES`] end with "and N others".
+///
+/// # Examples
+///
+/// ```
+/// use hello_rs::hello_all;
+/// assert_eq!(hello_all(&["Ana", "Bo", "Cy"]), "hello Ana, Bo, and Cy");
+/// ```
+pub fn hello_all(names: &[&str]) -> String {
+    hello_all_in(&Locale::default(), names)
+}
+
+/// Greet several people at once in the given locale
+///
+/// # Examples
+///
+/// ```
+/// use hello_rs::{hello_all_in, Locale};
+/// let fr = Locale::parse("fr").unwrap();
+/// assert_eq!(hello_all_in(&fr, &["Ana", "Bo", "Cy"]), "bonjour Ana, Bo et Cy");
+/// ```
+pub fn hello_all_in(locale: &Locale, names: &[&str]) -> String {
+    let mut out = String::new();
+    write_hello_all_in(&mut out, locale, names).expect("writing to a String cannot fail");
+    out
+}
+
+/// Write the greeting [`hello_all_in`] returns into `out` without
+/// allocating
+pub fn write_hello_all_in<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    locale: &Locale,
+    names: &[&str],
+) -> fmt::Result {
+    out.write_str(Formality::Neutral.salutation(locale))?;
+    out.write_char(' ')?;
+    ListFormat::new(locale)
+        .max_items(DEFAULT_MAX_NAMES)
+        .write(names, out)
+}
+
+/// Greet someone after checking their name against the default
+/// [`NamePolicy`]
+///
+/// # Examples
+///
+/// ```
+/// use hello_rs::{try_hello, HelloError};
+/// assert_eq!(try_hello("world"), Ok("hello world".to_string()));
+/// assert_eq!(try_hello(""), Err(HelloError::Empty));
+/// ```
+pub fn try_hello(name: &str) -> Result<String, HelloError> {
+    try_hello_with(&NamePolicy::default(), name)
+}
+
+/// Greet someone after checking their name against `policy`
+pub fn try_hello_with(policy: &NamePolicy, name: &str) -> Result<String, HelloError> {
+    policy.validate(name)?;
+    Ok(hello(name))
 }
 
 #[cfg(test)]
@@ -19,4 +254,73 @@ mod tests {
         assert_eq!(hello("claude"), "hello claude");
         assert_eq!(hello("world"), "hello world");
     }
+
+    #[test]
+    fn test_writers() {
+        let mut out = String::from("> ");
+        write_hello(&mut out, "ana").unwrap();
+        assert_eq!(out, "> hello ana");
+
+        let mut buf = String::new();
+        assert_eq!(hello_into(&mut buf, "a long name"), "hello a long name");
+        let capacity = buf.capacity();
+        assert_eq!(hello_into(&mut buf, "bo"), hello("bo"));
+        assert_eq!(buf.capacity(), capacity);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_hello_io() {
+        let mut bytes = Vec::new();
+        write_hello_io(&mut bytes, "bö").unwrap();
+        assert_eq!(bytes, "hello bö".as_bytes());
+    }
+
+    #[test]
+    fn test_hello_in() {
+        let hello_pt_br = |name| hello_in(&Locale::parse("pt-BR").unwrap(), name);
+        assert_eq!(hello_pt_br("ana"), "olá ana");
+        assert_eq!(hello_in(&Locale::default(), "world"), hello("world"));
+    }
+
+    #[test]
+    fn test_hello_now() {
+        // 2024-03-31T07:00:00Z, Easter Sunday
+        let easter = ZonedTime::new(1_711_868_400, UtcOffset::UTC);
+        let en = Locale::default();
+        assert_eq!(hello_now(&FixedClock(easter), &en, "bo"), "happy Easter bo");
+        // ...but still Saturday night in Los Angeles
+        let la = ZonedTime::new(easter.unix_seconds(), UtcOffset::from_hours(-8));
+        assert_eq!(hello_now(&FixedClock(la), &en, "bo"), "good evening bo");
+        // The ends of time still get a greeting rather than an overflow
+        for unix_seconds in [i64::MAX, i64::MIN] {
+            for minutes in [i32::MAX, 60, -60, i32::MIN] {
+                let time = ZonedTime::new(unix_seconds, UtcOffset::from_minutes(minutes));
+                assert!(hello_now(&FixedClock(time), &en, "bo").ends_with(" bo"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_hello_all() {
+        assert_eq!(hello_all(&["claude"]), hello("claude"));
+        assert_eq!(hello_all(&["Ana", "Bo"]), "hello Ana and Bo");
+        let names: Vec<String> = (1..=22).map(|i| format!("n{i}")).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        assert!(hello_all(&names).ends_with("n9, n10, and 12 others"));
+    }
+
+    #[test]
+    fn test_try_hello() {
+        assert_eq!(try_hello("claude"), Ok(hello("claude")));
+        assert_eq!(
+            try_hello("a\nb"),
+            Err(HelloError::ControlCharacters { offset: 1 })
+        );
+        let policy = NamePolicy::new().max_length(3);
+        assert_eq!(
+            try_hello_with(&policy, "claude"),
+            Err(HelloError::TooLong { length: 6, max: 3 })
+        );
+    }
 }
@@ -0,0 +1,155 @@
+//! Joining several names into one locale-correct list.
+
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::{catalog, Locale};
+
+/// Joins items the way a locale writes lists, e.g. "Ana, Bo, and Cy" in
+/// English and "Ana, Bo y Cy" in Spanish.
+///
+/// ```
+/// use hello_rs::{ListFormat, Locale};
+///
+/// let en = ListFormat::new(&Locale::default());
+/// assert_eq!(en.format(&["Ana", "Bo", "Cy"]), "Ana, Bo, and Cy");
+///
+/// let es = ListFormat::new(&Locale::parse("es").unwrap()).max_items(2);
+/// assert_eq!(es.format(&["Ana", "Bo", "Cy", "Di"]), "Ana, Bo y 2 más");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListFormat {
+    locale: Locale,
+    max_items: Option<usize>,
+}
+
+impl ListFormat {
+    /// List every item using the conventions of `locale`
+    pub fn new(locale: &Locale) -> Self {
+        ListFormat {
+            locale: locale.clone(),
+            max_items: None,
+        }
+    }
+
+    /// Show at most `max_items` items and summarize the rest as
+    /// "N others". A single leftover item is shown rather than summarized.
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Join `items` into a new string
+    pub fn format(&self, items: &[&str]) -> String {
+        let mut out = String::new();
+        self.write(items, &mut out)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// Join `items` into `out`
+    pub fn write<W: Write + ?Sized>(&self, items: &[&str], out: &mut W) -> fmt::Result {
+        let shown = match self.max_items {
+            Some(max) if items.len() > max.saturating_add(1) => &items[..max],
+            _ => items,
+        };
+        let others = items.len() - shown.len();
+        let count = shown.len() + usize::from(others > 0);
+
+        for (i, item) in shown.iter().enumerate() {
+            if i > 0 {
+                let next = if i + 1 == count { Some(*item) } else { None };
+                out.write_str(self.separator(i, count, next))?;
+            }
+            out.write_str(item)?;
+        }
+        if others > 0 {
+            if count > 1 {
+                out.write_str(self.separator(count - 1, count, None))?;
+            }
+            let pattern = catalog::message(&self.locale, "list.others");
+            let (before, after) = pattern.split_once("{n}").unwrap_or((pattern, ""));
+            write!(out, "{before}{others}{after}")?;
+        }
+        Ok(())
+    }
+
+    /// The text before the item at `index` in a list of `count` items.
+    /// `last` is the final item, when known, for languages whose conjunction
+    /// depends on the word that follows it.
+    fn separator(&self, index: usize, count: usize, last: Option<&str>) -> &'static str {
+        if index + 1 < count {
+            return ", ";
+        }
+        // Spanish "y" becomes "e" before an /i/ sound: "Ana e Isabel"
+        if self.locale.language() == "es" && last.is_some_and(starts_with_i_sound) {
+            return " e ";
+        }
+        if count == 2 {
+            catalog::message(&self.locale, "list.two")
+        } else {
+            catalog::message(&self.locale, "list.end")
+        }
+    }
+}
+
+fn starts_with_i_sound(word: &str) -> bool {
+    let lower = word.to_lowercase();
+    let lower = lower.strip_prefix('h').unwrap_or(&lower);
+    (lower.starts_with('i') || lower.starts_with('í')) && !lower.starts_with("ie")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(tag: &str, items: &[&str]) -> String {
+        ListFormat::new(&Locale::parse(tag).unwrap()).format(items)
+    }
+
+    #[test]
+    fn test_english() {
+        assert_eq!(format("en", &[]), "");
+        assert_eq!(format("en", &["Ana"]), "Ana");
+        assert_eq!(format("en", &["Ana", "Bo"]), "Ana and Bo");
+        assert_eq!(format("en", &["Ana", "Bo", "Cy"]), "Ana, Bo, and Cy");
+    }
+
+    #[test]
+    fn test_other_locales() {
+        assert_eq!(format("de", &["Ana", "Bo", "Cy"]), "Ana, Bo und Cy");
+        assert_eq!(format("fr", &["Ana", "Bo"]), "Ana et Bo");
+        assert_eq!(format("pt-BR", &["Ana", "Bo", "Cy"]), "Ana, Bo e Cy");
+        assert_eq!(format("tr", &["Ana", "Bo"]), "Ana ve Bo");
+    }
+
+    #[test]
+    fn test_spanish_conjunction() {
+        assert_eq!(format("es", &["Ana", "Bo"]), "Ana y Bo");
+        assert_eq!(format("es", &["Ana", "Isabel"]), "Ana e Isabel");
+        assert_eq!(format("es", &["Ana", "Bo", "Hilda"]), "Ana, Bo e Hilda");
+        assert_eq!(format("es", &["Ana", "Hielo"]), "Ana y Hielo");
+    }
+
+    #[test]
+    fn test_collapse() {
+        let names = ["Ana", "Bo", "Cy", "Di", "Ed"];
+        let en = ListFormat::new(&Locale::default());
+        assert_eq!(
+            en.clone().max_items(2).format(&names),
+            "Ana, Bo, and 3 others"
+        );
+        assert_eq!(en.clone().max_items(0).format(&names), "5 others");
+        // one leftover name is shown instead of "1 others"
+        assert_eq!(
+            en.clone().max_items(4).format(&names),
+            "Ana, Bo, Cy, Di, and Ed"
+        );
+        assert_eq!(
+            en.clone().max_items(usize::MAX).format(&names),
+            "Ana, Bo, Cy, Di, and Ed"
+        );
+        let it = ListFormat::new(&Locale::parse("it").unwrap()).max_items(1);
+        assert_eq!(it.format(&names), "Ana e altri 4");
+    }
+}
@@ -0,0 +1,215 @@
+//! Locale identifiers and the fallback rules used to pick translations.
+
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
+
+use crate::catalog;
+
+/// A BCP 47 style language tag such as `en`, `pt-BR` or `zh-Hant-TW`.
+///
+/// Only the language, script and region subtags are understood. Tags are
+/// normalized on parsing, so `pt_br` and `pt-BR` are the same locale.
+///
+/// ```
+/// use hello_rs::Locale;
+///
+/// let locale: Locale = "pt_br".parse().unwrap();
+/// assert_eq!(locale.as_str(), "pt-BR");
+/// assert_eq!(locale.fallback_chain().collect::<Vec<_>>(), ["pt-BR", "pt", "en"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "uniffi",
+    derive(uniffi::Object),
+    uniffi::export(Display, Eq, Hash)
+)]
+pub struct Locale {
+    tag: String,
+}
+
+#[cfg_attr(feature = "uniffi", uniffi::export)]
+impl Locale {
+    /// Parse and normalize a language tag
+    #[cfg_attr(feature = "uniffi", uniffi::constructor)]
+    pub fn parse(tag: &str) -> Result<Self, LocaleError> {
+        let invalid = || LocaleError {
+            tag: tag.to_string(),
+        };
+        let mut subtags = tag.split(['-', '_']);
+
+        let language = subtags.next().ok_or_else(invalid)?;
+        if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic())
+        {
+            return Err(invalid());
+        }
+        let mut normalized = language.to_ascii_lowercase();
+
+        let mut next = subtags.next();
+        if let Some(script) =
+            next.filter(|s| s.len() == 4 && s.chars().all(|c| c.is_ascii_alphabetic()))
+        {
+            normalized.push('-');
+            normalized.push_str(&script[..1].to_ascii_uppercase());
+            normalized.push_str(&script[1..].to_ascii_lowercase());
+            next = subtags.next();
+        }
+        if let Some(region) = next {
+            let alpha = region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic());
+            let numeric = region.len() == 3 && region.chars().all(|c| c.is_ascii_digit());
+            if !alpha && !numeric {
+                return Err(invalid());
+            }
+            normalized.push('-');
+            normalized.push_str(&region.to_ascii_uppercase());
+        }
+        if subtags.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(Locale { tag: normalized })
+    }
+
+    /// Whether the bundled catalog has messages for this exact tag
+    pub fn is_supported(&self) -> bool {
+        catalog::locales().any(|tag| tag == self.tag)
+    }
+}
+
+impl Locale {
+    /// The normalized tag, e.g. "pt-BR"
+    pub fn as_str(&self) -> &str {
+        &self.tag
+    }
+
+    /// The language subtag, e.g. "pt"
+    pub fn language(&self) -> &str {
+        self.tag.split('-').next().unwrap_or_default()
+    }
+
+    /// The region subtag, e.g. "BR", if there is one
+    pub fn region(&self) -> Option<&str> {
+        self.tag.split('-').skip(1).find(|s| s.len() != 4)
+    }
+
+    /// The tags consulted, most specific first, when looking up a message.
+    ///
+    /// Subtags are dropped from the right one at a time, and English is
+    /// always the last resort.
+    pub fn fallback_chain(&self) -> impl Iterator<Item = &str> {
+        let prefixes = core::iter::successors(Some(self.tag.as_str()), |tag| {
+            tag.rfind('-').map(|i| &tag[..i])
+        });
+        prefixes.chain((self.language() != "en").then_some("en"))
+    }
+}
+
+impl Default for Locale {
+    /// English, the language every message is guaranteed to exist in
+    fn default() -> Self {
+        Locale {
+            tag: "en".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.tag)
+    }
+}
+
+impl FromStr for Locale {
+    type Err = LocaleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Locale::parse(s)
+    }
+}
+
+/// Serialized as its normalized tag, e.g. `"pt-BR"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for Locale {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.tag)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Locale {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tag = String::deserialize(deserializer)?;
+        Locale::parse(&tag).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Returned when a string is not a usable language tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Object), uniffi::export(Display))]
+pub struct LocaleError {
+    tag: String,
+}
+
+impl fmt::Display for LocaleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid locale tag: {:?}", self.tag)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LocaleError {}
+
+/// The locales that have messages in the bundled catalog
+///
+/// ```
+/// let tags: Vec<String> = hello_rs::supported_locales().map(|l| l.to_string()).collect();
+/// assert!(tags.contains(&"pt-BR".to_string()));
+/// ```
+pub fn supported_locales() -> impl Iterator<Item = Locale> {
+    catalog::locales().map(|tag| Locale {
+        tag: tag.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_normalizes() {
+        assert_eq!(Locale::parse("EN").unwrap().as_str(), "en");
+        assert_eq!(Locale::parse("pt_br").unwrap().as_str(), "pt-BR");
+        assert_eq!(Locale::parse("zh-hant-tw").unwrap().as_str(), "zh-Hant-TW");
+        assert_eq!(Locale::parse("es-419").unwrap().region(), Some("419"));
+    }
+
+    #[test]
+    fn test_parse_rejects_garbage() {
+        for tag in ["", "e", "english", "en-", "en-USA-x", "1234"] {
+            assert!(Locale::parse(tag).is_err(), "{tag:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn test_fallback_chain() {
+        let chain = |tag: &str| {
+            Locale::parse(tag)
+                .unwrap()
+                .fallback_chain()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(chain("pt-BR"), ["pt-BR", "pt", "en"]);
+        assert_eq!(chain("zh-Hant-TW"), ["zh-Hant-TW", "zh-Hant", "zh", "en"]);
+        assert_eq!(chain("en-GB"), ["en-GB", "en"]);
+        assert_eq!(chain("en"), ["en"]);
+    }
+
+    #[test]
+    fn test_supported_locales() {
+        let supported: Vec<Locale> = supported_locales().collect();
+        assert!(supported.contains(&Locale::default()));
+        assert!(supported.iter().all(Locale::is_supported));
+        assert!(!Locale::parse("xx").unwrap().is_supported());
+    }
+}
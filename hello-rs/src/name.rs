@@ -0,0 +1,390 @@
+//! Personal names split into their parts.
+//!
+//! Parsing is heuristic: honorifics and suffixes are recognized from short
+//! lists, name particles such as "van" are kept with the family name, and
+//! whether the family name comes first depends on the locale.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+use crate::format::DEFAULT_PARTICLES;
+use crate::greeter::Greeter;
+use crate::Locale;
+
+/// Titles recognized before a name, compared without case or a final "."
+const HONORIFICS: &[&str] = &[
+    "dame", "don", "doña", "dr", "fr", "frau", "herr", "hon", "miss", "mlle", "mme", "mr", "mrs",
+    "ms", "mx", "prof", "rev", "sir", "sr", "sra", "srta",
+];
+
+/// Generational and professional suffixes recognized after a name
+const SUFFIXES: &[&str] = &[
+    "dds", "esq", "ii", "iii", "iv", "jr", "mbe", "md", "obe", "phd", "sr",
+];
+
+/// Which of the given and family names is written first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NameOrder {
+    /// "Jane Public", as in most European languages
+    #[default]
+    GivenFirst,
+    /// "Zhang Wei", as in Chinese, Japanese, Korean, Hungarian and Vietnamese
+    FamilyFirst,
+}
+
+impl NameOrder {
+    /// The order names are usually written in for `locale`'s language
+    pub fn for_locale(locale: &Locale) -> Self {
+        match locale.language() {
+            "zh" | "ja" | "ko" | "hu" | "vi" | "mn" => NameOrder::FamilyFirst,
+            _ => NameOrder::GivenFirst,
+        }
+    }
+}
+
+/// How to address someone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AddressForm {
+    /// The given name alone, e.g. "Jane"
+    Given,
+    /// The honorific and family name, e.g. "Dr. Public"
+    Family,
+    /// The given, middle and family names in the name's order, e.g.
+    /// "Jane Q. Public"
+    #[default]
+    Full,
+    /// Every part of the name, e.g. "Dr. Jane Q. Public Jr."
+    Formal,
+}
+
+/// A personal name split into its parts.
+///
+/// ```
+/// use hello_rs::{AddressForm, Locale, PersonName};
+///
+/// let name = PersonName::parse("Dr. Jane Q. Public Jr.", &Locale::default());
+/// assert_eq!(name.honorific(), Some("Dr."));
+/// assert_eq!(name.given(), Some("Jane"));
+/// assert_eq!(name.family(), Some("Public"));
+/// assert_eq!(name.address(AddressForm::Family), "Dr. Public");
+///
+/// let name = PersonName::parse("Zhang Wei", &Locale::parse("zh").unwrap());
+/// assert_eq!(name.given(), Some("Wei"));
+/// assert_eq!(name.address(AddressForm::Full), "Zhang Wei");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PersonName {
+    honorific: Option<String>,
+    given: Option<String>,
+    middle: Option<String>,
+    family: Option<String>,
+    suffix: Option<String>,
+    order: NameOrder,
+}
+
+impl PersonName {
+    /// Split `text` into name parts, using the name order of `locale`
+    ///
+    /// "Family, Given" with a comma is understood in any locale.
+    pub fn parse(text: &str, locale: &Locale) -> Self {
+        PersonName::parse_with_order(text, NameOrder::for_locale(locale))
+    }
+
+    /// Split `text` into name parts written in `order`
+    pub fn parse_with_order(text: &str, order: NameOrder) -> Self {
+        let mut name = PersonName {
+            order,
+            ..PersonName::default()
+        };
+
+        let (inverted_family, text) = match text.split_once(',') {
+            Some((family, rest)) if !rest.split_whitespace().all(is_suffix) => (Some(family), rest),
+            _ => (None, text),
+        };
+        let mut words: Vec<&str> = text
+            .split_whitespace()
+            .map(|word| word.trim_end_matches(','))
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        let honorifics = words.iter().take_while(|w| is_honorific(w)).count();
+        // a lone word is a name even if it looks like a title
+        let honorifics = honorifics.min(words.len().saturating_sub(1));
+        name.honorific = join(&words[..honorifics]);
+        words.drain(..honorifics);
+
+        let suffixes = words.iter().rev().take_while(|w| is_suffix(w)).count();
+        let suffixes = suffixes.min(words.len().saturating_sub(1));
+        name.suffix = join(&words[words.len() - suffixes..]);
+        words.truncate(words.len() - suffixes);
+
+        if let Some(family) = inverted_family {
+            name.family = join(&family.split_whitespace().collect::<Vec<_>>());
+            name.given = join(&words[..words.len().min(1)]);
+            name.middle = join(words.get(1..).unwrap_or_default());
+            return name;
+        }
+
+        match (order, words.len()) {
+            (_, 0) => {}
+            (_, 1) => name.given = join(&words),
+            (NameOrder::GivenFirst, n) => {
+                // "Ludwig van Beethoven": the family name starts at the first
+                // particle after the given name
+                let family_start = words[1..n - 1]
+                    .iter()
+                    .position(|w| is_particle(w))
+                    .map_or(n - 1, |i| i + 1);
+                name.given = join(&words[..1]);
+                name.middle = join(&words[1..family_start]);
+                name.family = join(&words[family_start..]);
+            }
+            (NameOrder::FamilyFirst, n) => {
+                name.family = join(&words[..1]);
+                name.middle = join(&words[1..n - 1]);
+                name.given = join(&words[n - 1..]);
+            }
+        }
+        name
+    }
+
+    /// A title such as "Dr." or "Prof. Dr."
+    pub fn honorific(&self) -> Option<&str> {
+        self.honorific.as_deref()
+    }
+
+    /// The given (first) name
+    pub fn given(&self) -> Option<&str> {
+        self.given.as_deref()
+    }
+
+    /// Any names between the given and family names
+    pub fn middle(&self) -> Option<&str> {
+        self.middle.as_deref()
+    }
+
+    /// The family name, including particles such as "van der"
+    pub fn family(&self) -> Option<&str> {
+        self.family.as_deref()
+    }
+
+    /// A suffix such as "Jr." or "PhD"
+    pub fn suffix(&self) -> Option<&str> {
+        self.suffix.as_deref()
+    }
+
+    /// Whether the family name is written first
+    pub fn order(&self) -> NameOrder {
+        self.order
+    }
+
+    /// The name as it would be used to address someone in `form`
+    pub fn address(&self, form: AddressForm) -> String {
+        let mut out = String::new();
+        self.write_address(form, &mut out)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// Write the name as it would be used to address someone in `form`
+    ///
+    /// Forms that need a part the name doesn't have fall back to the parts
+    /// it does have, so "Given" for "Dr. Public" is "Public".
+    pub fn write_address<W: Write + ?Sized>(&self, form: AddressForm, out: &mut W) -> fmt::Result {
+        let full = self.full_parts();
+        let parts: [Option<&str>; 5] = match form {
+            AddressForm::Given if self.given.is_some() => [self.given(), None, None, None, None],
+            AddressForm::Given => full,
+            AddressForm::Family if self.family.is_some() => {
+                [self.honorific(), self.family(), None, None, None]
+            }
+            AddressForm::Family => [self.honorific(), full[0], full[1], full[2], None],
+            AddressForm::Full => full,
+            AddressForm::Formal => [self.honorific(), full[0], full[1], full[2], self.suffix()],
+        };
+        let mut first = true;
+        for part in parts.into_iter().flatten() {
+            if !first {
+                out.write_char(' ')?;
+            }
+            out.write_str(part)?;
+            first = false;
+        }
+        Ok(())
+    }
+
+    /// The given, middle and family names in the name's order
+    fn full_parts(&self) -> [Option<&str>; 5] {
+        match self.order {
+            NameOrder::GivenFirst => [self.given(), self.middle(), self.family(), None, None],
+            NameOrder::FamilyFirst => [self.family(), self.middle(), self.given(), None, None],
+        }
+    }
+}
+
+impl fmt::Display for PersonName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_address(AddressForm::Formal, f)
+    }
+}
+
+impl FromStr for PersonName {
+    type Err = core::convert::Infallible;
+
+    /// Parse with [`NameOrder::GivenFirst`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(PersonName::parse_with_order(s, NameOrder::GivenFirst))
+    }
+}
+
+/// A [`Greeter`] that parses the name it is given and passes one form of it
+/// on to another greeter.
+///
+/// ```
+/// use hello_rs::{AddressForm, Addressed, FormalGreeter, Greeter, Locale};
+///
+/// let polite = Addressed::new(AddressForm::Family, FormalGreeter);
+/// assert_eq!(polite.greet(&Locale::default(), "Dr. Jane Public"), "Good Day, Dr. Public.");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Addressed<G> {
+    form: AddressForm,
+    greeter: G,
+}
+
+impl<G: Greeter> Addressed<G> {
+    /// Greet with `greeter`, addressing people in `form`
+    pub const fn new(form: AddressForm, greeter: G) -> Self {
+        Addressed { form, greeter }
+    }
+}
+
+impl<G: Greeter> Greeter for Addressed<G> {
+    fn write_greeting(&self, out: &mut dyn Write, locale: &Locale, name: &str) -> fmt::Result {
+        let address = PersonName::parse(name, locale).address(self.form);
+        self.greeter.write_greeting(out, locale, &address)
+    }
+}
+
+fn join(words: &[&str]) -> Option<String> {
+    if words.is_empty() {
+        None
+    } else {
+        Some(words.join(" "))
+    }
+}
+
+fn matches_any(word: &str, list: &[&str]) -> bool {
+    let word = word.trim_end_matches(['.', ',']);
+    list.iter().any(|item| {
+        // compare case-insensitively without allocating, including "Doña"
+        item.chars()
+            .flat_map(char::to_lowercase)
+            .eq(word.chars().flat_map(char::to_lowercase))
+    })
+}
+
+fn is_honorific(word: &str) -> bool {
+    matches_any(word, HONORIFICS)
+}
+
+fn is_suffix(word: &str) -> bool {
+    matches_any(word, SUFFIXES)
+}
+
+fn is_particle(word: &str) -> bool {
+    matches_any(word, DEFAULT_PARTICLES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> PersonName {
+        PersonName::parse(text, &Locale::default())
+    }
+
+    #[test]
+    fn test_parts() {
+        let name = parse("Dr. Jane Q. Public Jr.");
+        assert_eq!(name.honorific(), Some("Dr."));
+        assert_eq!(name.given(), Some("Jane"));
+        assert_eq!(name.middle(), Some("Q."));
+        assert_eq!(name.family(), Some("Public"));
+        assert_eq!(name.suffix(), Some("Jr."));
+        assert_eq!(name.to_string(), "Dr. Jane Q. Public Jr.");
+
+        let name = parse("  ana  ");
+        assert_eq!(name.given(), Some("ana"));
+        assert_eq!(name.family(), None);
+        assert_eq!(parse("Dr.").given(), Some("Dr."));
+        assert_eq!(parse(""), PersonName::default());
+    }
+
+    #[test]
+    fn test_particles() {
+        let name = parse("Ludwig van Beethoven");
+        assert_eq!(name.given(), Some("Ludwig"));
+        assert_eq!(name.middle(), None);
+        assert_eq!(name.family(), Some("van Beethoven"));
+        let name = parse("Anne-Marie Louise van der Berg");
+        assert_eq!(name.middle(), Some("Louise"));
+        assert_eq!(name.family(), Some("van der Berg"));
+    }
+
+    #[test]
+    fn test_comma_inversion() {
+        let name = parse("Public, Jane Q.");
+        assert_eq!(name.given(), Some("Jane"));
+        assert_eq!(name.middle(), Some("Q."));
+        assert_eq!(name.family(), Some("Public"));
+        // a comma before a suffix is not an inversion
+        let name = parse("Martin Luther King, Jr.");
+        assert_eq!(name.family(), Some("King"));
+        assert_eq!(name.suffix(), Some("Jr."));
+    }
+
+    #[test]
+    fn test_family_first() {
+        let ja = Locale::parse("ja").unwrap();
+        let name = PersonName::parse("Yamada Taro", &ja);
+        assert_eq!(name.family(), Some("Yamada"));
+        assert_eq!(name.given(), Some("Taro"));
+        assert_eq!(name.order(), NameOrder::FamilyFirst);
+        assert_eq!(name.address(AddressForm::Full), "Yamada Taro");
+        assert_eq!(name.address(AddressForm::Given), "Taro");
+
+        let vi = Locale::parse("vi").unwrap();
+        let name = PersonName::parse("Nguyễn Văn An", &vi);
+        assert_eq!(name.middle(), Some("Văn"));
+        assert_eq!(name.given(), Some("An"));
+    }
+
+    #[test]
+    fn test_address_forms() {
+        let name = parse("Prof. Dr. Ada Lovelace PhD");
+        assert_eq!(name.honorific(), Some("Prof. Dr."));
+        assert_eq!(name.address(AddressForm::Given), "Ada");
+        assert_eq!(name.address(AddressForm::Family), "Prof. Dr. Lovelace");
+        assert_eq!(name.address(AddressForm::Full), "Ada Lovelace");
+        assert_eq!(
+            name.address(AddressForm::Formal),
+            "Prof. Dr. Ada Lovelace PhD"
+        );
+
+        let only_given = parse("Mx. Sam");
+        assert_eq!(only_given.address(AddressForm::Family), "Mx. Sam");
+        assert_eq!(only_given.address(AddressForm::Given), "Sam");
+    }
+
+    #[test]
+    fn test_addressed_greeter() {
+        let friendly = Addressed::new(AddressForm::Given, crate::FancyGreeter);
+        assert_eq!(
+            friendly.greet(&Locale::default(), "dr. jane public"),
+            "Hello Jane!"
+        );
+    }
+}
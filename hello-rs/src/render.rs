@@ -0,0 +1,348 @@
+//! Rendering greetings for the places they end up.
+//!
+//! A [`Greeting`] displays as plain text. The renderers here escape it for
+//! HTML, Markdown, a terminal or JSON, so consumers don't each need their own
+//! escaping, and mark up the recipient where the format allows it.
+
+use alloc::string::{String, ToString};
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+use crate::{Greeting, Part};
+
+/// A way of turning a [`Greeting`] into text.
+///
+/// ```
+/// use hello_rs::{Greeting, Html, Markdown, Render};
+///
+/// let greeting = Greeting::builder("<ana>").punctuation("!").build();
+/// assert_eq!(Html.render(&greeting), r#"<span lang="en">hello <bdi>&lt;ana&gt;</bdi>!</span>"#);
+/// assert_eq!(Markdown.render(&greeting), r"hello **\<ana\>**!");
+/// ```
+pub trait Render {
+    /// Write `greeting` into `out`
+    fn write(&self, greeting: &Greeting, out: &mut dyn Write) -> fmt::Result;
+
+    /// `greeting` rendered into a new string
+    fn render(&self, greeting: &Greeting) -> String {
+        let mut out = String::new();
+        self.write(greeting, &mut out)
+            .expect("writing to a String cannot fail");
+        out
+    }
+}
+
+/// HTML with the text escaped, tagged with the greeting's language and the
+/// recipient isolated in a `<bdi>` element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Html;
+
+impl Render for Html {
+    fn write(&self, greeting: &Greeting, out: &mut dyn Write) -> fmt::Result {
+        out.write_str("<span lang=\"")?;
+        write_html_escaped(&greeting.locale().to_string(), out)?;
+        out.write_str("\">")?;
+        greeting.write_parts(|part, text| {
+            if part == Part::Recipient {
+                out.write_str("<bdi>")?;
+                write_html_escaped(text, out)?;
+                return out.write_str("</bdi>");
+            }
+            write_html_escaped(text, out)
+        })?;
+        out.write_str("</span>")
+    }
+}
+
+fn write_html_escaped(text: &str, out: &mut dyn Write) -> fmt::Result {
+    for c in text.chars() {
+        match c {
+            '&' => out.write_str("&amp;")?,
+            '<' => out.write_str("&lt;")?,
+            '>' => out.write_str("&gt;")?,
+            '"' => out.write_str("&quot;")?,
+            '\'' => out.write_str("&#39;")?,
+            _ => out.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+/// Markdown with the text escaped and the recipient in bold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Markdown;
+
+impl Render for Markdown {
+    fn write(&self, greeting: &Greeting, out: &mut dyn Write) -> fmt::Result {
+        greeting.write_parts(|part, text| {
+            if part == Part::Recipient {
+                out.write_str("**")?;
+                write_markdown_escaped(text, out)?;
+                return out.write_str("**");
+            }
+            write_markdown_escaped(text, out)
+        })
+    }
+}
+
+fn write_markdown_escaped(text: &str, out: &mut dyn Write) -> fmt::Result {
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '~' | '|' | '&' | '#'
+        ) {
+            out.write_char('\\')?;
+        }
+        out.write_char(c)?;
+    }
+    Ok(())
+}
+
+/// Terminal text with the recipient in bold.
+///
+/// Control characters are replaced with U+FFFD so a name can't smuggle in
+/// escape sequences of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Ansi;
+
+impl Render for Ansi {
+    fn write(&self, greeting: &Greeting, out: &mut dyn Write) -> fmt::Result {
+        greeting.write_parts(|part, text| {
+            if part == Part::Recipient {
+                out.write_str("\x1b[1m")?;
+                write_ansi_escaped(text, out)?;
+                return out.write_str("\x1b[22m");
+            }
+            write_ansi_escaped(text, out)
+        })
+    }
+}
+
+fn write_ansi_escaped(text: &str, out: &mut dyn Write) -> fmt::Result {
+    for c in text.chars() {
+        out.write_char(if c.is_control() { '\u{FFFD}' } else { c })?;
+    }
+    Ok(())
+}
+
+/// A JSON object with the rendered text and the parts it was made from.
+///
+/// ```
+/// use hello_rs::{Greeting, Json, Render};
+///
+/// let greeting = Greeting::builder("ana").punctuation("!").build();
+/// assert_eq!(
+///     Json.render(&greeting),
+///     r#"{"text":"hello ana!","salutation":"hello","recipient":"ana","locale":"en","formality":"neutral"}"#
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Json;
+
+impl Render for Json {
+    fn write(&self, greeting: &Greeting, out: &mut dyn Write) -> fmt::Result {
+        let mut text = String::new();
+        let mut salutation = String::new();
+        let mut recipient = String::new();
+        greeting.write_parts(|part, part_text| {
+            text.push_str(part_text);
+            match part {
+                Part::Salutation => salutation.push_str(part_text),
+                Part::Recipient => recipient.push_str(part_text),
+                _ => {}
+            }
+            Ok(())
+        })?;
+        let fields = [
+            ("text", text.as_str()),
+            ("salutation", &salutation),
+            ("recipient", &recipient),
+            ("locale", &greeting.locale().to_string()),
+            ("formality", greeting.formality().as_str()),
+        ];
+        out.write_char('{')?;
+        for (i, (key, value)) in fields.iter().enumerate() {
+            if i > 0 {
+                out.write_char(',')?;
+            }
+            write_json_string(key, out)?;
+            out.write_char(':')?;
+            write_json_string(value, out)?;
+        }
+        out.write_char('}')
+    }
+}
+
+fn write_json_string(text: &str, out: &mut dyn Write) -> fmt::Result {
+    out.write_char('"')?;
+    for c in text.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            c if c < ' ' => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+/// The renderers by name, for bindings that pick one at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum Format {
+    /// The [`Display`](fmt::Display) text, unescaped
+    #[default]
+    Plain,
+    /// See [`Html`]
+    Html,
+    /// See [`Markdown`]
+    Markdown,
+    /// See [`Ansi`]
+    Ansi,
+    /// See [`Json`]
+    Json,
+}
+
+impl Format {
+    /// Every format
+    pub const ALL: [Format; 5] = [
+        Format::Plain,
+        Format::Html,
+        Format::Markdown,
+        Format::Ansi,
+        Format::Json,
+    ];
+
+    /// The lowercase name of the format, e.g. "html"
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Format::Plain => "plain",
+            Format::Html => "html",
+            Format::Markdown => "markdown",
+            Format::Ansi => "ansi",
+            Format::Json => "json",
+        }
+    }
+}
+
+impl Render for Format {
+    fn write(&self, greeting: &Greeting, out: &mut dyn Write) -> fmt::Result {
+        match self {
+            Format::Plain => write!(out, "{greeting}"),
+            Format::Html => Html.write(greeting, out),
+            Format::Markdown => Markdown.write(greeting, out),
+            Format::Ansi => Ansi.write(greeting, out),
+            Format::Json => Json.write(greeting, out),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Format {
+    type Err = FormatError;
+
+    /// Parse a name produced by [`Format::as_str`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Format::ALL
+            .into_iter()
+            .find(|format| format.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| FormatError { name: s.into() })
+    }
+}
+
+/// The error returned when parsing a [`Format`] from an unknown name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatError {
+    name: String,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown format: {:?}", self.name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FormatError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BidiIsolation, Capitalization, Formality, Locale};
+
+    fn greeting(name: &str) -> Greeting {
+        Greeting::builder(name)
+            .formality(Formality::Formal)
+            .capitalization(Capitalization::Sentence)
+            .punctuation("!")
+            .build()
+    }
+
+    #[test]
+    fn test_html() {
+        assert_eq!(
+            Html.render(&greeting(r#"Tom & "Jerry""#)),
+            r#"<span lang="en">Good day, <bdi>Tom &amp; &quot;Jerry&quot;</bdi>!</span>"#
+        );
+        let de = Greeting::builder("o'neill")
+            .locale(Locale::parse("de-AT").unwrap())
+            .build();
+        assert_eq!(
+            Html.render(&de),
+            r#"<span lang="de-AT">hallo <bdi>o&#39;neill</bdi></span>"#
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        assert_eq!(
+            Markdown.render(&greeting("*bold* [link](x)")),
+            r"Good day, **\*bold\* \[link\](x)**!"
+        );
+        assert_eq!(Markdown.render(&Greeting::new("")), "hello ");
+    }
+
+    #[test]
+    fn test_ansi() {
+        assert_eq!(
+            Ansi.render(&greeting("ana\x1b[31m")),
+            "Good day, \x1b[1mana\u{FFFD}[31m\x1b[22m!"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let greeting = Greeting::builder("\"ana\"\n")
+            .bidi(BidiIsolation::new())
+            .build();
+        assert_eq!(
+            Json.render(&greeting),
+            r#"{"text":"hello \"ana\"\n","salutation":"hello","recipient":"\"ana\"\n","locale":"en","formality":"neutral"}"#
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let greeting = greeting("ana");
+        assert_eq!(Format::Plain.render(&greeting), greeting.to_string());
+        for format in Format::ALL {
+            assert_eq!(format.as_str().parse(), Ok(format));
+        }
+        assert_eq!(Format::Html.render(&greeting), Html.render(&greeting));
+        let error = "yaml".parse::<Format>().unwrap_err();
+        assert_eq!(error.to_string(), r#"unknown format: "yaml""#);
+    }
+}
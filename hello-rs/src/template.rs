@@ -0,0 +1,544 @@
+//! User-defined greeting formats such as `"{salutation}, {name|title}{punct}"`.
+//!
+//! A template is parsed once by [`Template::compile`] into a list of literal
+//! slices and placeholders, so rendering only walks that list and never
+//! allocates per placeholder. The parser is a `const fn`, which lets the
+//! [`template!`](crate::template!) macro reject a malformed literal template
+//! at compile time.
+//!
+//! # Syntax
+//!
+//! - `{salutation}`, `{name}` and `{punct}` are replaced by the greeting's
+//!   salutation, recipient and punctuation.
+//! - Filters follow a placeholder, separated by `|`: `trim` removes
+//!   surrounding whitespace, and `lower`, `upper` and `title` change case
+//!   using the greeting's locale. At most one case filter may be used per
+//!   placeholder.
+//! - `{{` and `}}` stand for literal braces.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+use crate::format::{CaseRules, TitleCase};
+use crate::Greeting;
+
+/// Why a template could not be compiled.
+///
+/// Offsets are byte offsets into the template source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// A `{` has no matching `}`
+    UnclosedPlaceholder {
+        /// Byte offset of the `{`
+        offset: usize,
+    },
+    /// A `}` has no matching `{`; write `}}` for a literal brace
+    UnmatchedBrace {
+        /// Byte offset of the `}`
+        offset: usize,
+    },
+    /// A placeholder other than `salutation`, `name` or `punct`
+    UnknownPlaceholder {
+        /// The placeholder as written
+        name: String,
+        /// Byte offset of the placeholder's name
+        offset: usize,
+    },
+    /// A filter other than `trim`, `lower`, `upper` or `title`
+    UnknownFilter {
+        /// The filter as written
+        name: String,
+        /// Byte offset of the filter's name
+        offset: usize,
+    },
+    /// A second case filter on the same placeholder, e.g. `{name|upper|lower}`
+    ConflictingFilters {
+        /// The second case filter
+        name: String,
+        /// Byte offset of the second case filter
+        offset: usize,
+    },
+}
+
+impl TemplateError {
+    /// Byte offset in the template source where the problem was found
+    pub fn offset(&self) -> usize {
+        match self {
+            TemplateError::UnclosedPlaceholder { offset }
+            | TemplateError::UnmatchedBrace { offset }
+            | TemplateError::UnknownPlaceholder { offset, .. }
+            | TemplateError::UnknownFilter { offset, .. }
+            | TemplateError::ConflictingFilters { offset, .. } => *offset,
+        }
+    }
+
+    fn new(source: &str, error: ParseError) -> Self {
+        let name = source[error.start..error.end].to_string();
+        let offset = error.start;
+        match error.kind {
+            ErrorKind::UnclosedPlaceholder => TemplateError::UnclosedPlaceholder { offset },
+            ErrorKind::UnmatchedBrace => TemplateError::UnmatchedBrace { offset },
+            ErrorKind::UnknownPlaceholder => TemplateError::UnknownPlaceholder { name, offset },
+            ErrorKind::UnknownFilter => TemplateError::UnknownFilter { name, offset },
+            ErrorKind::ConflictingFilters => TemplateError::ConflictingFilters { name, offset },
+        }
+    }
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::UnclosedPlaceholder { offset } => {
+                write!(f, "unclosed placeholder at byte {offset}")
+            }
+            TemplateError::UnmatchedBrace { offset } => {
+                write!(f, "unmatched '}}' at byte {offset}")
+            }
+            TemplateError::UnknownPlaceholder { name, offset } => {
+                write!(f, "unknown placeholder {name:?} at byte {offset}")
+            }
+            TemplateError::UnknownFilter { name, offset } => {
+                write!(f, "unknown filter {name:?} at byte {offset}")
+            }
+            TemplateError::ConflictingFilters { name, offset } => {
+                write!(
+                    f,
+                    "filter {name:?} at byte {offset} conflicts with an earlier case filter"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TemplateError {}
+
+/// A compiled greeting template.
+///
+/// ```
+/// use hello_rs::{Greeting, Template};
+///
+/// let template = Template::compile("{salutation|title}, {name|upper}{punct}").unwrap();
+/// let greeting = Greeting::builder("ana").punctuation("!").build();
+/// assert_eq!(template.render(&greeting), "Hello, ANA!");
+///
+/// let error = Template::compile("{salutation} {nmae}").unwrap_err();
+/// assert_eq!(error.to_string(), r#"unknown placeholder "nmae" at byte 14"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    source: String,
+    segments: Vec<Segment>,
+    title_case: TitleCase,
+}
+
+impl Template {
+    /// Parse `source` into a template
+    pub fn compile(source: &str) -> Result<Self, TemplateError> {
+        let mut segments = Vec::new();
+        let mut pos = 0;
+        while pos < source.len() {
+            match next_segment(source.as_bytes(), pos) {
+                Ok((segment, next)) => {
+                    segments.push(segment);
+                    pos = next;
+                }
+                Err(error) => return Err(TemplateError::new(source, error)),
+            }
+        }
+        Ok(Template {
+            source: source.to_string(),
+            segments,
+            title_case: TitleCase::new(),
+        })
+    }
+
+    /// The source the template was compiled from
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Render `greeting` into a new string
+    pub fn render(&self, greeting: &Greeting) -> String {
+        let mut out = String::new();
+        self.write(greeting, &mut out)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// Render `greeting` into `out`
+    pub fn write<W: Write + ?Sized>(&self, greeting: &Greeting, out: &mut W) -> fmt::Result {
+        let rules = CaseRules::for_locale(greeting.locale());
+        for segment in &self.segments {
+            match *segment {
+                Segment::Literal { start, end } => out.write_str(&self.source[start..end])?,
+                Segment::Field { field, trim, case } => {
+                    let value = match field {
+                        Field::Salutation => greeting.salutation(),
+                        Field::Name => greeting.recipient(),
+                        Field::Punct => greeting.punctuation(),
+                    };
+                    let value = if trim { value.trim() } else { value };
+                    // The name is isolated and stripped as the greeting's
+                    // bidi setting says, so the filters can't undo it
+                    let isolated;
+                    let value = match (field, greeting.bidi()) {
+                        (Field::Name, Some(bidi)) => {
+                            isolated = bidi.apply(value);
+                            isolated.as_str()
+                        }
+                        _ => value,
+                    };
+                    match case {
+                        Case::AsIs => out.write_str(value)?,
+                        Case::Lower => value.chars().try_for_each(|c| rules.write_lower(c, out))?,
+                        Case::Upper => value.chars().try_for_each(|c| rules.write_upper(c, out))?,
+                        Case::Title => self.title_case.write_with_rules(rules, value, out)?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Template::compile(s)
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Serialized as its source, e.g. `"{salutation}, {name|title}!"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for Template {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Template {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let source = String::deserialize(deserializer)?;
+        Template::compile(&source).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Compile a template whose syntax is checked at compile time.
+///
+/// The argument must be a constant string; a malformed template fails the
+/// build instead of returning a [`TemplateError`] at runtime.
+///
+/// ```
+/// let template = hello_rs::template!("{salutation} {name|title}!");
+/// assert_eq!(template.render(&hello_rs::Greeting::new("ana")), "hello Ana!");
+/// ```
+///
+/// ```compile_fail
+/// let template = hello_rs::template!("{salutation} {nmae}");
+/// ```
+#[macro_export]
+macro_rules! template {
+    ($source:expr) => {{
+        const _: () = $crate::__check_template($source);
+        $crate::Template::compile($source).expect("template was checked at compile time")
+    }};
+}
+
+/// Panic, at compile time when called in a `const`, if `source` is not a
+/// valid template. Used by [`template!`](crate::template!).
+#[doc(hidden)]
+pub const fn __check_template(source: &str) {
+    let bytes = source.as_bytes();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        pos = match next_segment(bytes, pos) {
+            Ok((_, next)) => next,
+            Err(error) => match error.kind {
+                ErrorKind::UnclosedPlaceholder => panic!("template has an unclosed placeholder"),
+                ErrorKind::UnmatchedBrace => panic!("template has an unmatched '}}'"),
+                ErrorKind::UnknownPlaceholder => panic!("template has an unknown placeholder"),
+                ErrorKind::UnknownFilter => panic!("template has an unknown filter"),
+                ErrorKind::ConflictingFilters => panic!("template has conflicting case filters"),
+            },
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment {
+    /// `source[start..end]`, copied as is
+    Literal { start: usize, end: usize },
+    Field {
+        field: Field,
+        trim: bool,
+        case: Case,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Salutation,
+    Name,
+    Punct,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Case {
+    AsIs,
+    Lower,
+    Upper,
+    Title,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ErrorKind {
+    UnclosedPlaceholder,
+    UnmatchedBrace,
+    UnknownPlaceholder,
+    UnknownFilter,
+    ConflictingFilters,
+}
+
+/// A parse error covering `source[start..end]`. Kept `Copy` and free of
+/// `String`s so the parser can run in a `const` context.
+#[derive(Debug, Clone, Copy)]
+struct ParseError {
+    kind: ErrorKind,
+    start: usize,
+    end: usize,
+}
+
+impl ParseError {
+    const fn new(kind: ErrorKind, start: usize, end: usize) -> Self {
+        ParseError { kind, start, end }
+    }
+}
+
+/// Parse the segment starting at `pos`, returning it with the position
+/// just past it
+const fn next_segment(src: &[u8], pos: usize) -> Result<(Segment, usize), ParseError> {
+    let escaped = pos + 1 < src.len() && src[pos + 1] == src[pos];
+    match src[pos] {
+        b'{' | b'}' if escaped => Ok((
+            Segment::Literal {
+                start: pos,
+                end: pos + 1,
+            },
+            pos + 2,
+        )),
+        b'{' => placeholder(src, pos),
+        b'}' => Err(ParseError::new(ErrorKind::UnmatchedBrace, pos, pos + 1)),
+        _ => {
+            let mut end = pos;
+            while end < src.len() && src[end] != b'{' && src[end] != b'}' {
+                end += 1;
+            }
+            Ok((Segment::Literal { start: pos, end }, end))
+        }
+    }
+}
+
+/// Parse the placeholder whose `{` is at `open`
+const fn placeholder(src: &[u8], open: usize) -> Result<(Segment, usize), ParseError> {
+    let mut close = open + 1;
+    while close < src.len() && src[close] != b'}' {
+        if src[close] == b'{' {
+            break;
+        }
+        close += 1;
+    }
+    if close == src.len() || src[close] != b'}' {
+        return Err(ParseError::new(
+            ErrorKind::UnclosedPlaceholder,
+            open,
+            open + 1,
+        ));
+    }
+
+    let start = open + 1;
+    let end = part_end(src, start, close);
+    let field = if is(src, start, end, b"salutation") {
+        Field::Salutation
+    } else if is(src, start, end, b"name") {
+        Field::Name
+    } else if is(src, start, end, b"punct") {
+        Field::Punct
+    } else {
+        return Err(ParseError::new(ErrorKind::UnknownPlaceholder, start, end));
+    };
+
+    let mut trim = false;
+    let mut case = Case::AsIs;
+    let mut end = end;
+    while end < close {
+        let start = end + 1;
+        end = part_end(src, start, close);
+        let filter_case = if is(src, start, end, b"trim") {
+            trim = true;
+            continue;
+        } else if is(src, start, end, b"lower") {
+            Case::Lower
+        } else if is(src, start, end, b"upper") {
+            Case::Upper
+        } else if is(src, start, end, b"title") {
+            Case::Title
+        } else {
+            return Err(ParseError::new(ErrorKind::UnknownFilter, start, end));
+        };
+        if !matches!(case, Case::AsIs) {
+            return Err(ParseError::new(ErrorKind::ConflictingFilters, start, end));
+        }
+        case = filter_case;
+    }
+    Ok((Segment::Field { field, trim, case }, close + 1))
+}
+
+/// The end of the `|`-separated part of a placeholder starting at `start`
+const fn part_end(src: &[u8], start: usize, close: usize) -> usize {
+    let mut end = start;
+    while end < close && src[end] != b'|' {
+        end += 1;
+    }
+    end
+}
+
+/// Whether `src[start..end]` is `word`
+const fn is(src: &[u8], start: usize, end: usize, word: &[u8]) -> bool {
+    if end - start != word.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < word.len() {
+        if src[start + i] != word[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BidiIsolation, Formality, Locale};
+
+    fn render(source: &str, greeting: &Greeting) -> String {
+        Template::compile(source).unwrap().render(greeting)
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let greeting = Greeting::builder("ana").punctuation("!").build();
+        assert_eq!(
+            render("{salutation} {name}{punct}", &greeting),
+            "hello ana!"
+        );
+        assert_eq!(render("", &greeting), "");
+        assert_eq!(render("{{{name}}}", &greeting), "{ana}");
+        assert_eq!(render("¡{name}!", &greeting), "¡ana!");
+    }
+
+    #[test]
+    fn test_filters() {
+        let greeting = Greeting::builder("  anne-marie van der berg ")
+            .formality(Formality::Formal)
+            .build();
+        assert_eq!(
+            render("{salutation|upper}, {name|trim|title}.", &greeting),
+            "GOOD DAY, Anne-Marie van der Berg."
+        );
+        assert_eq!(
+            render("<{name|title|trim}>", &greeting),
+            "<Anne-Marie van der Berg>"
+        );
+
+        let turkish = Greeting::builder("ismail")
+            .locale(Locale::parse("tr").unwrap())
+            .build();
+        assert_eq!(render("{name|upper}", &turkish), "İSMAİL");
+        assert_eq!(render("{name|title}", &turkish), "İsmail");
+    }
+
+    #[test]
+    fn test_bidi() {
+        let greeting = Greeting::builder("محمد")
+            .punctuation("!")
+            .bidi(BidiIsolation::new())
+            .build();
+        assert_eq!(
+            render("{salutation} {name}{punct}", &greeting),
+            greeting.to_string()
+        );
+        assert_eq!(
+            render("{salutation|upper} {name|upper}{punct}", &greeting),
+            "HELLO \u{2068}محمد\u{2069}!"
+        );
+
+        let greeting = Greeting::builder(" ana\u{202E}bo ")
+            .bidi(BidiIsolation::new().isolate(false).strip_controls(true))
+            .build();
+        assert_eq!(
+            render("{salutation} {name|trim|title}", &greeting),
+            "hello Anabo"
+        );
+        // Without a bidi setting the name is written as given
+        assert_eq!(render("{name}", &Greeting::new("محمد")), "محمد");
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |source| Template::compile(source).unwrap_err();
+        assert_eq!(
+            error("hello {name"),
+            TemplateError::UnclosedPlaceholder { offset: 6 }
+        );
+        assert_eq!(
+            error("{name{punct}"),
+            TemplateError::UnclosedPlaceholder { offset: 0 }
+        );
+        assert_eq!(
+            error("hello }"),
+            TemplateError::UnmatchedBrace { offset: 6 }
+        );
+        assert_eq!(
+            error("{}"),
+            TemplateError::UnknownPlaceholder {
+                name: String::new(),
+                offset: 1
+            }
+        );
+        assert_eq!(
+            error("{name|title|shout}"),
+            TemplateError::UnknownFilter {
+                name: "shout".to_string(),
+                offset: 12
+            }
+        );
+        assert_eq!(
+            error("é {name|upper|lower}"),
+            TemplateError::ConflictingFilters {
+                name: "lower".to_string(),
+                offset: 15
+            }
+        );
+        assert_eq!(error("{name|}").offset(), 6);
+    }
+
+    #[test]
+    fn test_macro() {
+        let template = crate::template!("{salutation|title} {name}");
+        assert_eq!(template.to_string(), "{salutation|title} {name}");
+        assert_eq!(template.render(&Greeting::new("bo")), "Hello bo");
+    }
+}
@@ -0,0 +1,262 @@
+//! Small Unicode helpers shared by the formatting modules.
+//!
+//! These are compact approximations of the Unicode algorithms rather than
+//! full implementations, so that hello-rs keeps its no-dependencies promise.
+//! They cover combining marks, emoji sequences, regional-indicator flags and
+//! East Asian display widths in the scripts people actually type names in.
+
+/// Combining marks and other characters that never start a grapheme cluster
+const EXTEND: &[(char, char)] = &[
+    ('\u{0300}', '\u{036F}'),
+    ('\u{0483}', '\u{0489}'),
+    ('\u{0591}', '\u{05BD}'),
+    ('\u{05BF}', '\u{05BF}'),
+    ('\u{05C1}', '\u{05C2}'),
+    ('\u{05C4}', '\u{05C5}'),
+    ('\u{05C7}', '\u{05C7}'),
+    ('\u{0610}', '\u{061A}'),
+    ('\u{064B}', '\u{065F}'),
+    ('\u{0670}', '\u{0670}'),
+    ('\u{06D6}', '\u{06DC}'),
+    ('\u{06DF}', '\u{06E4}'),
+    ('\u{06E7}', '\u{06E8}'),
+    ('\u{06EA}', '\u{06ED}'),
+    ('\u{0711}', '\u{0711}'),
+    ('\u{0730}', '\u{074A}'),
+    ('\u{0900}', '\u{0903}'),
+    ('\u{093A}', '\u{093C}'),
+    ('\u{093E}', '\u{094F}'),
+    ('\u{0951}', '\u{0957}'),
+    ('\u{0962}', '\u{0963}'),
+    ('\u{0981}', '\u{0983}'),
+    ('\u{09BC}', '\u{09BC}'),
+    ('\u{09BE}', '\u{09CD}'),
+    ('\u{0E31}', '\u{0E31}'),
+    ('\u{0E34}', '\u{0E3A}'),
+    ('\u{0E47}', '\u{0E4E}'),
+    ('\u{1160}', '\u{11FF}'),
+    ('\u{1AB0}', '\u{1AFF}'),
+    ('\u{1DC0}', '\u{1DFF}'),
+    ('\u{200C}', '\u{200C}'),
+    ('\u{20D0}', '\u{20FF}'),
+    ('\u{302A}', '\u{302F}'),
+    ('\u{3099}', '\u{309A}'),
+    ('\u{FE00}', '\u{FE0F}'),
+    ('\u{FE20}', '\u{FE2F}'),
+    ('\u{1F3FB}', '\u{1F3FF}'),
+    ('\u{E0020}', '\u{E007F}'),
+    ('\u{E0100}', '\u{E01EF}'),
+];
+
+/// Blocks made up mostly of emoji and other pictographs
+const PICTOGRAPHIC: &[(char, char)] = &[
+    ('\u{2600}', '\u{27BF}'),
+    ('\u{1F000}', '\u{1F2FF}'),
+    ('\u{1F300}', '\u{1F3FA}'),
+    ('\u{1F400}', '\u{1FAFF}'),
+];
+
+/// Characters that take two terminal columns: East Asian wide and
+/// fullwidth forms, and emoji that default to emoji presentation
+const WIDE: &[(char, char)] = &[
+    ('\u{1100}', '\u{115F}'),
+    ('\u{231A}', '\u{231B}'),
+    ('\u{2329}', '\u{232A}'),
+    ('\u{23E9}', '\u{23EC}'),
+    ('\u{23F0}', '\u{23F0}'),
+    ('\u{23F3}', '\u{23F3}'),
+    ('\u{25FD}', '\u{25FE}'),
+    ('\u{2614}', '\u{2615}'),
+    ('\u{2648}', '\u{2653}'),
+    ('\u{267F}', '\u{267F}'),
+    ('\u{2693}', '\u{2693}'),
+    ('\u{26A1}', '\u{26A1}'),
+    ('\u{26AA}', '\u{26AB}'),
+    ('\u{26BD}', '\u{26BE}'),
+    ('\u{26C4}', '\u{26C5}'),
+    ('\u{26CE}', '\u{26CE}'),
+    ('\u{26D4}', '\u{26D4}'),
+    ('\u{26EA}', '\u{26EA}'),
+    ('\u{26F2}', '\u{26F3}'),
+    ('\u{26F5}', '\u{26F5}'),
+    ('\u{26FA}', '\u{26FA}'),
+    ('\u{26FD}', '\u{26FD}'),
+    ('\u{2705}', '\u{2705}'),
+    ('\u{270A}', '\u{270B}'),
+    ('\u{2728}', '\u{2728}'),
+    ('\u{274C}', '\u{274C}'),
+    ('\u{274E}', '\u{274E}'),
+    ('\u{2753}', '\u{2755}'),
+    ('\u{2757}', '\u{2757}'),
+    ('\u{2795}', '\u{2797}'),
+    ('\u{27B0}', '\u{27B0}'),
+    ('\u{27BF}', '\u{27BF}'),
+    ('\u{2B1B}', '\u{2B1C}'),
+    ('\u{2B50}', '\u{2B50}'),
+    ('\u{2B55}', '\u{2B55}'),
+    ('\u{2E80}', '\u{303E}'),
+    ('\u{3041}', '\u{33FF}'),
+    ('\u{3400}', '\u{4DBF}'),
+    ('\u{4E00}', '\u{9FFF}'),
+    ('\u{A000}', '\u{A4CF}'),
+    ('\u{A960}', '\u{A97F}'),
+    ('\u{AC00}', '\u{D7A3}'),
+    ('\u{F900}', '\u{FAFF}'),
+    ('\u{FE10}', '\u{FE19}'),
+    ('\u{FE30}', '\u{FE6F}'),
+    ('\u{FF00}', '\u{FF60}'),
+    ('\u{FFE0}', '\u{FFE6}'),
+    ('\u{1F004}', '\u{1F004}'),
+    ('\u{1F0CF}', '\u{1F0CF}'),
+    ('\u{1F18E}', '\u{1F18E}'),
+    ('\u{1F191}', '\u{1F19A}'),
+    ('\u{1F1E6}', '\u{1F1FF}'),
+    ('\u{1F200}', '\u{1F2FF}'),
+    ('\u{1F300}', '\u{1F64F}'),
+    ('\u{1F680}', '\u{1F6FF}'),
+    ('\u{1F900}', '\u{1F9FF}'),
+    ('\u{1FA70}', '\u{1FAFF}'),
+    ('\u{20000}', '\u{2FFFD}'),
+    ('\u{30000}', '\u{3FFFD}'),
+];
+
+/// Invisible formatting characters: zero-width spaces and joiners,
+/// directional marks, embeddings and isolates, and the byte order mark
+const ZERO_WIDTH: &[(char, char)] = &[
+    ('\u{00AD}', '\u{00AD}'),
+    ('\u{200B}', '\u{200F}'),
+    ('\u{2028}', '\u{202E}'),
+    ('\u{2060}', '\u{206F}'),
+    ('\u{FEFF}', '\u{FEFF}'),
+];
+
+/// Emoji presentation selector, which widens the character before it
+const VS16: char = '\u{FE0F}';
+
+const ZWJ: char = '\u{200D}';
+
+pub(crate) fn in_ranges(c: char, ranges: &[(char, char)]) -> bool {
+    ranges
+        .binary_search_by(|&(lo, hi)| {
+            if hi < c {
+                core::cmp::Ordering::Less
+            } else if lo > c {
+                core::cmp::Ordering::Greater
+            } else {
+                core::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Whether `c` attaches to the preceding character instead of standing alone
+pub(crate) fn is_extend(c: char) -> bool {
+    in_ranges(c, EXTEND)
+}
+
+pub(crate) fn is_pictographic(c: char) -> bool {
+    in_ranges(c, PICTOGRAPHIC)
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+/// The number of terminal columns a grapheme cluster takes: 0, 1 or 2
+pub(crate) fn cluster_width(cluster: &str) -> usize {
+    let Some(base) = cluster.chars().next() else {
+        return 0;
+    };
+    if base.is_control() || is_extend(base) || in_ranges(base, ZERO_WIDTH) {
+        0
+    } else if in_ranges(base, WIDE) || (is_pictographic(base) && cluster.contains(VS16)) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Split `s` into (approximate) extended grapheme clusters
+pub(crate) fn graphemes(s: &str) -> Graphemes<'_> {
+    Graphemes { rest: s }
+}
+
+pub(crate) struct Graphemes<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Graphemes<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut chars = self.rest.char_indices();
+        let (_, mut prev) = chars.next()?;
+        let mut regional_indicators = usize::from(is_regional_indicator(prev));
+        let mut end = self.rest.len();
+
+        for (i, c) in chars {
+            let joins = is_extend(c)
+                || c == ZWJ
+                || (prev == ZWJ && is_pictographic(c))
+                || (prev == '\r' && c == '\n')
+                || (is_regional_indicator(c) && regional_indicators % 2 == 1);
+            if !joins {
+                end = i;
+                break;
+            }
+            if is_regional_indicator(c) {
+                regional_indicators += 1;
+            }
+            prev = c;
+        }
+
+        let (cluster, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(cluster)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(s: &str) -> Vec<&str> {
+        graphemes(s).collect()
+    }
+
+    #[test]
+    fn test_graphemes() {
+        assert_eq!(split("abc"), ["a", "b", "c"]);
+        assert_eq!(split("e\u{301}mile"), ["e\u{301}", "m", "i", "l", "e"]);
+        assert_eq!(split("\r\nx"), ["\r\n", "x"]);
+        assert_eq!(split(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_cluster_width() {
+        assert_eq!(cluster_width("a"), 1);
+        assert_eq!(cluster_width("e\u{301}"), 1);
+        assert_eq!(cluster_width("李"), 2);
+        assert_eq!(cluster_width("\u{FF21}"), 2);
+        assert_eq!(cluster_width("\u{1F44B}\u{1F3FD}"), 2);
+        // heart: text presentation, then emoji presentation
+        assert_eq!(cluster_width("\u{2764}"), 1);
+        assert_eq!(cluster_width("\u{2764}\u{FE0F}"), 2);
+        assert_eq!(cluster_width("\u{2068}"), 0);
+        assert_eq!(cluster_width("\n"), 0);
+    }
+
+    #[test]
+    fn test_emoji_sequences() {
+        // family: man, ZWJ, woman, ZWJ, girl
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        assert_eq!(split(family), [family]);
+        // waving hand with a skin tone modifier
+        assert_eq!(split("\u{1F44B}\u{1F3FD}!"), ["\u{1F44B}\u{1F3FD}", "!"]);
+        // two flags: Brazil and Portugal
+        assert_eq!(
+            split("\u{1F1E7}\u{1F1F7}\u{1F1F5}\u{1F1F9}"),
+            ["\u{1F1E7}\u{1F1F7}", "\u{1F1F5}\u{1F1F9}"]
+        );
+    }
+}
@@ -0,0 +1,430 @@
+//! Validation of names before they are greeted.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::bidi::is_bidi_control;
+
+/// Why a name was rejected by a [`NamePolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HelloError {
+    /// The name is empty or only whitespace
+    Empty,
+    /// The name has more characters than the policy allows
+    TooLong {
+        /// Number of characters in the name
+        length: usize,
+        /// The policy's limit
+        max: usize,
+    },
+    /// The name contains a control character such as a newline
+    ControlCharacters {
+        /// Byte offset of the first control character
+        offset: usize,
+    },
+    /// The name contains a bidi embedding, override or isolate, which can
+    /// reorder the text around it
+    UnsafeBidi {
+        /// Byte offset of the first bidi control
+        offset: usize,
+    },
+    /// The name mixes scripts in a way that is typical of spoofing, such as
+    /// Latin with Cyrillic look-alikes
+    InvalidScriptMix {
+        /// The first script in the name
+        first: Script,
+        /// The script that may not be combined with it
+        second: Script,
+    },
+}
+
+impl fmt::Display for HelloError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HelloError::Empty => write!(f, "name is empty"),
+            HelloError::TooLong { length, max } => {
+                write!(f, "name is {length} characters long, the limit is {max}")
+            }
+            HelloError::ControlCharacters { offset } => {
+                write!(f, "name contains a control character at byte {offset}")
+            }
+            HelloError::UnsafeBidi { offset } => {
+                write!(f, "name contains a bidi control character at byte {offset}")
+            }
+            HelloError::InvalidScriptMix { first, second } => {
+                write!(f, "name mixes {first:?} and {second:?} scripts")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HelloError {}
+
+/// The writing system a character belongs to, as far as name validation
+/// cares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum Script {
+    /// Digits, punctuation, spaces, marks and anything not listed below
+    Common,
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Bengali,
+    Thai,
+    Georgian,
+    Hangul,
+    Hiragana,
+    Katakana,
+    Bopomofo,
+    Han,
+}
+
+const SCRIPT_RANGES: &[(char, char, Script)] = &[
+    ('A', 'Z', Script::Latin),
+    ('a', 'z', Script::Latin),
+    ('\u{00AA}', '\u{00AA}', Script::Latin),
+    ('\u{00BA}', '\u{00BA}', Script::Latin),
+    ('\u{00C0}', '\u{024F}', Script::Latin),
+    ('\u{0370}', '\u{03FF}', Script::Greek),
+    ('\u{0400}', '\u{052F}', Script::Cyrillic),
+    ('\u{0530}', '\u{058F}', Script::Armenian),
+    ('\u{0590}', '\u{05FF}', Script::Hebrew),
+    ('\u{0600}', '\u{06FF}', Script::Arabic),
+    ('\u{0750}', '\u{077F}', Script::Arabic),
+    ('\u{08A0}', '\u{08FF}', Script::Arabic),
+    ('\u{0900}', '\u{097F}', Script::Devanagari),
+    ('\u{0980}', '\u{09FF}', Script::Bengali),
+    ('\u{0E00}', '\u{0E7F}', Script::Thai),
+    ('\u{10A0}', '\u{10FF}', Script::Georgian),
+    ('\u{1100}', '\u{11FF}', Script::Hangul),
+    ('\u{1C80}', '\u{1C8F}', Script::Cyrillic),
+    ('\u{1E00}', '\u{1EFF}', Script::Latin),
+    ('\u{1F00}', '\u{1FFF}', Script::Greek),
+    ('\u{2C60}', '\u{2C7F}', Script::Latin),
+    ('\u{2DE0}', '\u{2DFF}', Script::Cyrillic),
+    ('\u{2E80}', '\u{2FDF}', Script::Han),
+    ('\u{3040}', '\u{309F}', Script::Hiragana),
+    ('\u{30A0}', '\u{30FF}', Script::Katakana),
+    ('\u{3100}', '\u{312F}', Script::Bopomofo),
+    ('\u{3130}', '\u{318F}', Script::Hangul),
+    ('\u{31F0}', '\u{31FF}', Script::Katakana),
+    ('\u{3400}', '\u{4DBF}', Script::Han),
+    ('\u{4E00}', '\u{9FFF}', Script::Han),
+    ('\u{A640}', '\u{A69F}', Script::Cyrillic),
+    ('\u{A720}', '\u{A7FF}', Script::Latin),
+    ('\u{AC00}', '\u{D7AF}', Script::Hangul),
+    ('\u{F900}', '\u{FAFF}', Script::Han),
+    ('\u{FB1D}', '\u{FB4F}', Script::Hebrew),
+    ('\u{FB50}', '\u{FDFF}', Script::Arabic),
+    ('\u{FE70}', '\u{FEFF}', Script::Arabic),
+    ('\u{FF21}', '\u{FF3A}', Script::Latin),
+    ('\u{FF41}', '\u{FF5A}', Script::Latin),
+    ('\u{FF66}', '\u{FF9F}', Script::Katakana),
+    ('\u{20000}', '\u{2FFFF}', Script::Han),
+];
+
+impl Script {
+    /// The script of `c`; anything that isn't a letter is [`Script::Common`]
+    pub fn of(c: char) -> Script {
+        if !c.is_alphabetic() {
+            return Script::Common;
+        }
+        SCRIPT_RANGES
+            .binary_search_by(|&(lo, hi, _)| {
+                if hi < c {
+                    core::cmp::Ordering::Less
+                } else if lo > c {
+                    core::cmp::Ordering::Greater
+                } else {
+                    core::cmp::Ordering::Equal
+                }
+            })
+            .map_or(Script::Common, |i| SCRIPT_RANGES[i].2)
+    }
+}
+
+/// Scripts that are routinely written together, e.g. Japanese mixes kanji,
+/// hiragana and katakana. Latin may join any of them.
+const SCRIPT_FAMILIES: &[&[Script]] = &[
+    &[Script::Han, Script::Hiragana, Script::Katakana],
+    &[Script::Han, Script::Hangul],
+    &[Script::Han, Script::Bopomofo],
+];
+
+/// The rules a name must follow to be greeted by [`try_hello`](crate::try_hello).
+///
+/// ```
+/// use hello_rs::{HelloError, NamePolicy};
+///
+/// let policy = NamePolicy::new().max_length(8);
+/// assert!(policy.validate("Ana").is_ok());
+/// assert_eq!(
+///     policy.validate("Bartholomew"),
+///     Err(HelloError::TooLong { length: 11, max: 8 })
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamePolicy {
+    max_length: usize,
+    allow_empty: bool,
+    allow_control_characters: bool,
+    allow_bidi_controls: bool,
+    allow_mixed_scripts: bool,
+}
+
+impl NamePolicy {
+    /// The default limit on the number of characters in a name
+    pub const DEFAULT_MAX_LENGTH: usize = 128;
+
+    /// The strict default policy
+    pub fn new() -> Self {
+        NamePolicy {
+            max_length: Self::DEFAULT_MAX_LENGTH,
+            allow_empty: false,
+            allow_control_characters: false,
+            allow_bidi_controls: false,
+            allow_mixed_scripts: false,
+        }
+    }
+
+    /// A policy that accepts everything [`hello`](crate::hello) does
+    pub fn permissive() -> Self {
+        NamePolicy {
+            max_length: usize::MAX,
+            allow_empty: true,
+            allow_control_characters: true,
+            allow_bidi_controls: true,
+            allow_mixed_scripts: true,
+        }
+    }
+
+    /// Limit the number of characters in a name
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// Accept empty and whitespace-only names
+    pub fn allow_empty(mut self, allow: bool) -> Self {
+        self.allow_empty = allow;
+        self
+    }
+
+    /// Accept control characters such as newlines and tabs
+    pub fn allow_control_characters(mut self, allow: bool) -> Self {
+        self.allow_control_characters = allow;
+        self
+    }
+
+    /// Accept bidi embeddings, overrides and isolates
+    pub fn allow_bidi_controls(mut self, allow: bool) -> Self {
+        self.allow_bidi_controls = allow;
+        self
+    }
+
+    /// Accept any combination of scripts
+    pub fn allow_mixed_scripts(mut self, allow: bool) -> Self {
+        self.allow_mixed_scripts = allow;
+        self
+    }
+
+    /// Check `name` against the policy, reporting the first problem found
+    pub fn validate(&self, name: &str) -> Result<(), HelloError> {
+        if !self.allow_empty && name.trim().is_empty() {
+            return Err(HelloError::Empty);
+        }
+
+        // Every char is at most 4 bytes, so short names skip the count
+        if name.len() > self.max_length {
+            let length = name.chars().count();
+            if length > self.max_length {
+                return Err(HelloError::TooLong {
+                    length,
+                    max: self.max_length,
+                });
+            }
+        }
+
+        if !self.allow_control_characters {
+            if let Some(offset) = name.find(char::is_control) {
+                return Err(HelloError::ControlCharacters { offset });
+            }
+        }
+
+        if !self.allow_bidi_controls {
+            if let Some(offset) = name.find(is_bidi_control) {
+                return Err(HelloError::UnsafeBidi { offset });
+            }
+        }
+
+        if !self.allow_mixed_scripts {
+            check_scripts(name)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for NamePolicy {
+    fn default() -> Self {
+        NamePolicy::new()
+    }
+}
+
+/// Allow a single script, Latin plus scripts it isn't easily confused
+/// with, or one of the [`SCRIPT_FAMILIES`] (optionally with Latin).
+///
+/// The error names the first pair of scripts, in order of appearance, that
+/// can't be mixed.
+fn check_scripts(name: &str) -> Result<(), HelloError> {
+    let mut scripts: Vec<Script> = Vec::new();
+    for script in name.chars().map(Script::of) {
+        if script != Script::Common && !scripts.contains(&script) {
+            scripts.push(script);
+        }
+    }
+
+    for (i, &second) in scripts.iter().enumerate() {
+        if let Some(&first) = scripts[..i]
+            .iter()
+            .find(|&&first| !compatible(first, second))
+        {
+            return Err(HelloError::InvalidScriptMix { first, second });
+        }
+    }
+    Ok(())
+}
+
+/// Whether `a` and `b` may appear in the same name. The families only
+/// overlap in Han, so a set of scripts is allowed exactly when every pair
+/// in it is.
+fn compatible(a: Script, b: Script) -> bool {
+    match (a, b) {
+        (Script::Latin, other) | (other, Script::Latin) => {
+            !matches!(other, Script::Greek | Script::Cyrillic)
+        }
+        _ => SCRIPT_FAMILIES
+            .iter()
+            .any(|family| family.contains(&a) && family.contains(&b)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(name: &str) -> Result<(), HelloError> {
+        NamePolicy::new().validate(name)
+    }
+
+    #[test]
+    fn test_accepts_ordinary_names() {
+        for name in [
+            "world",
+            "Zoë",
+            "O'Neill",
+            "Anne-Marie van der Berg",
+            "张伟",
+            "Ελένη",
+        ] {
+            assert_eq!(check(name), Ok(()), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(check(""), Err(HelloError::Empty));
+        assert_eq!(check(" \t"), Err(HelloError::Empty));
+        assert!(NamePolicy::new().allow_empty(true).validate("").is_ok());
+    }
+
+    #[test]
+    fn test_too_long() {
+        let long = "a".repeat(NamePolicy::DEFAULT_MAX_LENGTH + 1);
+        assert_eq!(
+            check(&long),
+            Err(HelloError::TooLong {
+                length: 129,
+                max: 128
+            })
+        );
+        // 128 four-byte characters are within the limit
+        assert_eq!(check(&"𝒜".repeat(128)), Ok(()));
+    }
+
+    #[test]
+    fn test_control_characters() {
+        assert_eq!(
+            check("ana\nbo"),
+            Err(HelloError::ControlCharacters { offset: 3 })
+        );
+        assert!(NamePolicy::new()
+            .allow_control_characters(true)
+            .validate("ana\tbo")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_unsafe_bidi() {
+        assert_eq!(
+            check("ana\u{202E}txt.exe"),
+            Err(HelloError::UnsafeBidi { offset: 3 })
+        );
+        assert_eq!(
+            check("\u{2067}ana"),
+            Err(HelloError::UnsafeBidi { offset: 0 })
+        );
+        // directional marks don't reorder anything on their own
+        assert_eq!(check("ana\u{200F}"), Ok(()));
+    }
+
+    #[test]
+    fn test_script_mix() {
+        // Latin "p" and "ypal" around a Cyrillic "а"
+        assert_eq!(
+            check("p\u{0430}ypal"),
+            Err(HelloError::InvalidScriptMix {
+                first: Script::Latin,
+                second: Script::Cyrillic
+            })
+        );
+        assert!(check("Ελένη Ivanova").is_err());
+        // only Greek conflicts, and only with the Latin before it
+        assert_eq!(
+            check("Ana 山田 Ελ"),
+            Err(HelloError::InvalidScriptMix {
+                first: Script::Latin,
+                second: Script::Greek
+            })
+        );
+        assert_eq!(
+            check("山田 김민수 はなこ"),
+            Err(HelloError::InvalidScriptMix {
+                first: Script::Hangul,
+                second: Script::Hiragana
+            })
+        );
+        assert_eq!(check("山田 はなこ タロウ"), Ok(()));
+        assert_eq!(check("Kim 김민수"), Ok(()));
+        assert_eq!(check("Dana דנה"), Ok(()));
+        assert!(check("דנה دانة").is_err());
+        assert!(NamePolicy::new()
+            .allow_mixed_scripts(true)
+            .validate("p\u{0430}ypal")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_permissive_accepts_anything() {
+        let policy = NamePolicy::permissive();
+        for name in ["", "\n", "\u{202E}", "p\u{0430}ypal", &"a".repeat(10_000)] {
+            assert_eq!(policy.validate(name), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Checks that the core API builds and works without the `std` feature.
+//!
+//! Run with `cargo test --no-default-features --test no_std`. With the
+//! default features this file compiles to nothing.
+
+#![cfg(not(feature = "std"))]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::ToString;
+
+use hello_rs::format::title_case;
+use hello_rs::{
+    hello, hello_now, try_hello, Capitalization, FixedClock, Greeting, HelloError, Locale,
+    Template, UtcOffset, ZonedTime,
+};
+
+#[test]
+fn test_hello() {
+    assert_eq!(hello("world"), "hello world");
+    assert_eq!(try_hello(""), Err(HelloError::Empty));
+}
+
+#[test]
+fn test_formatting() {
+    let greeting = Greeting::builder("o'neill van der berg")
+        .capitalization(Capitalization::Title)
+        .locale(Locale::parse("en-GB").unwrap())
+        .build();
+    assert_eq!(greeting.to_string(), "Hello O'Neill van der Berg");
+    assert_eq!(title_case("anne-marie"), "Anne-Marie");
+
+    let template = Template::compile("{salutation|upper} {name}").unwrap();
+    assert_eq!(template.render(&Greeting::new("ana")), "HELLO ana");
+}
+
+#[test]
+fn test_clock() {
+    // 2024-12-25T10:00:00Z
+    let clock = FixedClock(ZonedTime::new(1_735_120_800, UtcOffset::UTC));
+    assert_eq!(
+        hello_now(&clock, &Locale::default(), "ana"),
+        "merry Christmas ana"
+    );
+}
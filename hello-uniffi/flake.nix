@@ -0,0 +1,129 @@
+{
+  description = "hello-uniffi - UniFFI bindings for hello-rs, generated for Kotlin";
+
+  inputs = {
+    nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+    flake-utils.url = "github:numtide/flake-utils";
+
+    # Source-only input - we just need the Rust code, not the flake outputs
+    hello-rs.url = "git+file:///Users/matt/src/hello-subflakes/subflake-git/hello-rs?ref=main";
+    hello-rs.flake = false;
+
+    poag = {
+      url = "path:../poag";
+      inputs.nixpkgs.follows = "nixpkgs";
+    };
+  };
+
+  outputs = { self, nixpkgs, flake-utils, hello-rs, poag }:
+    flake-utils.lib.eachDefaultSystem (system:
+      let
+        pkgs = import nixpkgs {
+          inherit system;
+        };
+
+        # Create source with hello-rs included from non-flake input
+        helloUniffiSrc = pkgs.runCommand "hello-uniffi-src" {} ''
+          mkdir -p $out
+          cp -r ${./.}/* $out/
+          chmod -R +w $out
+          mkdir -p $out/hello-rs
+          cp -r ${hello-rs}/* $out/hello-rs/
+          # Update the Cargo.toml path to point to ./hello-rs instead of ../hello-rs
+          sed -i 's|path = "../hello-rs"|path = "./hello-rs"|' $out/Cargo.toml
+        '';
+
+        libName = "libhello_uniffi${pkgs.stdenv.hostPlatform.extensions.sharedLibrary}";
+
+        # The shared library plus the Kotlin bindings generated from it
+        helloUniffi = pkgs.stdenv.mkDerivation {
+          pname = "hello-uniffi";
+          version = "0.1.0";
+          src = helloUniffiSrc;
+
+          cargoDeps = pkgs.rustPlatform.importCargoLock {
+            lockFile = ./Cargo.lock;
+          };
+
+          nativeBuildInputs = with pkgs; [
+            cargo
+            rustc
+            rustPlatform.cargoSetupHook
+          ];
+
+          buildPhase = ''
+            cargo build --release --offline
+            cargo run --release --offline --bin uniffi-bindgen -- generate \
+              --library target/release/${libName} --language kotlin \
+              --config uniffi.toml --out-dir kotlin
+          '';
+
+          installPhase = ''
+            mkdir -p $out/lib $out/kotlin
+            cp target/release/${libName} $out/lib/
+            cp -r kotlin/* $out/kotlin/
+          '';
+        };
+      in
+      {
+        packages = {
+          default = helloUniffi;
+          hello-uniffi = helloUniffi;
+        };
+
+        checks = {
+          # Compile the Kotlin test program against the generated bindings
+          # and run it on the JVM, replaying hello-rs's golden vectors from
+          # the same source the library was built from
+          kotlin-test = pkgs.stdenv.mkDerivation {
+            name = "hello-uniffi-kotlin-test";
+            src = ./tests;
+
+            nativeBuildInputs = with pkgs; [ kotlin jdk jq ];
+
+            buildPhase = ''
+              jq -r '.vectors[] | [.name, .locale, .style, .expected] | @tsv' \
+                ${hello-rs}/vectors/greetings.json > vectors.tsv
+              kotlinc HelloTest.kt ${helloUniffi}/kotlin/uniffi/hello/hello.kt \
+                ${helloUniffi}/kotlin/uniffi/hello_uniffi/hello_uniffi.kt \
+                -cp ${pkgs.jna}/share/java/jna.jar -include-runtime -d hello-test.jar
+              HELLO_RS_VECTORS_TSV=vectors.tsv java \
+                -Djna.library.path=${helloUniffi}/lib \
+                -cp hello-test.jar:${pkgs.jna}/share/java/jna.jar \
+                HelloTestKt 2>&1 | tee test-output.txt
+            '';
+
+            installPhase = ''
+              mkdir -p $out
+              cp test-output.txt $out/
+            '';
+          };
+        };
+
+        devShells.default = pkgs.mkShell {
+          packages = with pkgs; [
+            cargo
+            rustc
+            rust-analyzer
+            clippy
+            rustfmt
+            kotlin
+            jdk
+          ];
+          buildInputs = [
+            poag.packages.${system}.default
+          ];
+          shellHook = ''
+            export REPO_ROOT=$(pwd)
+            echo "hello-uniffi development environment"
+            echo ""
+            echo "To generate the Kotlin bindings:"
+            echo "  cargo build --release"
+            echo "  cargo run --bin uniffi-bindgen -- generate --library target/release/${libName} --language kotlin --config uniffi.toml --out-dir out"
+            echo ""
+            echo "To build and test:"
+            echo "  nix flake check"
+          '';
+        };
+      });
+}
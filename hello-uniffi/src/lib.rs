@@ -0,0 +1,280 @@
+//! UniFFI bindings for hello-rs
+//!
+//! hello-rs derives UniFFI's traits on its own types behind its `uniffi`
+//! feature, and exports the functions and methods that can cross the FFI as
+//! they are. This crate re-exports that scaffolding and adds what UniFFI
+//! can't take from hello-rs directly: [`HelloError`] with its lengths as
+//! u64s, [`Greeting`] and [`StyleRegistry`] without builders, iterators or
+//! borrowed return values, [`UnknownStyle`], since UniFFI can't throw errors
+//! defined in another crate, and the functions that take or return them. The
+//! shared library is read by uniffi-bindgen to generate the Kotlin bindings
+//! (and could generate Swift or Python ones).
+
+use std::fmt;
+use std::sync::Arc;
+
+use hello_rs::{BidiIsolation, Formality, Format, Locale, PersonName, Render, Script};
+
+hello_rs::uniffi_reexport_scaffolding!();
+uniffi::setup_scaffolding!();
+
+/// Why a name was rejected by hello-rs's default name policy
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Error)]
+pub enum HelloError {
+    /// The name is empty or only whitespace
+    Empty,
+    /// The name has more characters than the policy allows
+    TooLong {
+        /// Number of characters in the name
+        length: u64,
+        /// The policy's limit
+        max: u64,
+    },
+    /// The name contains a control character such as a newline
+    ControlCharacters {
+        /// Byte offset of the first control character
+        offset: u64,
+    },
+    /// The name contains a bidi embedding, override or isolate
+    UnsafeBidi {
+        /// Byte offset of the first bidi control
+        offset: u64,
+    },
+    /// The name mixes confusable scripts, e.g. Latin and Cyrillic
+    InvalidScriptMix {
+        /// The first script in the name
+        first: Script,
+        /// The script that may not be combined with it
+        second: Script,
+    },
+}
+
+impl fmt::Display for HelloError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HelloError::Empty => write!(f, "name is empty"),
+            HelloError::TooLong { length, max } => {
+                write!(f, "name is {length} characters long, the limit is {max}")
+            }
+            HelloError::ControlCharacters { offset } => {
+                write!(f, "name contains a control character at byte {offset}")
+            }
+            HelloError::UnsafeBidi { offset } => {
+                write!(f, "name contains a bidi control character at byte {offset}")
+            }
+            HelloError::InvalidScriptMix { first, second } => {
+                write!(f, "name mixes {first:?} and {second:?} scripts")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HelloError {}
+
+impl From<hello_rs::HelloError> for HelloError {
+    fn from(error: hello_rs::HelloError) -> Self {
+        match error {
+            hello_rs::HelloError::Empty => HelloError::Empty,
+            hello_rs::HelloError::TooLong { length, max } => HelloError::TooLong {
+                length: length as u64,
+                max: max as u64,
+            },
+            hello_rs::HelloError::ControlCharacters { offset } => HelloError::ControlCharacters {
+                offset: offset as u64,
+            },
+            hello_rs::HelloError::UnsafeBidi { offset } => HelloError::UnsafeBidi {
+                offset: offset as u64,
+            },
+            hello_rs::HelloError::InvalidScriptMix { first, second } => {
+                HelloError::InvalidScriptMix { first, second }
+            }
+        }
+    }
+}
+
+/// Greet someone after checking their name against hello-rs's default name
+/// policy
+#[uniffi::export]
+pub fn try_hello(name: &str) -> Result<String, HelloError> {
+    Ok(hello_rs::try_hello(name)?)
+}
+
+/// Greet several people at once, e.g. "hello Ana, Bo, and Cy", in English
+/// when `locale` is `None`
+#[uniffi::export(default(locale = None))]
+pub fn hello_all(names: Vec<String>, locale: Option<Arc<Locale>>) -> String {
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    hello_rs::hello_all_in(locale.as_deref().unwrap_or(&Locale::default()), &names)
+}
+
+/// The locales that have messages in hello-rs's bundled catalog
+#[uniffi::export]
+pub fn supported_locales() -> Vec<Arc<Locale>> {
+    hello_rs::supported_locales().map(Arc::new).collect()
+}
+
+/// Greeting styles looked up by name, starting with hello-rs's built-in ones
+#[derive(uniffi::Object)]
+pub struct StyleRegistry(hello_rs::StyleRegistry);
+
+#[uniffi::export]
+impl StyleRegistry {
+    /// A registry with the built-in styles
+    #[uniffi::constructor]
+    pub fn new() -> Self {
+        StyleRegistry(hello_rs::StyleRegistry::default())
+    }
+
+    /// Greet `name` in `locale` using the style registered as `style`
+    pub fn greet(&self, style: &str, locale: &Locale, name: &str) -> Result<String, UnknownStyle> {
+        self.0.greet(style, locale, name).map_err(UnknownStyle)
+    }
+
+    /// The names of the registered styles, in the order they were added
+    pub fn names(&self) -> Vec<String> {
+        self.0.names().map(String::from).collect()
+    }
+}
+
+impl Default for StyleRegistry {
+    fn default() -> Self {
+        StyleRegistry::new()
+    }
+}
+
+/// The error returned when a [`StyleRegistry`] has no style by that name
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Object)]
+#[uniffi::export(Display)]
+pub struct UnknownStyle(hello_rs::UnknownStyle);
+
+impl fmt::Display for UnknownStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for UnknownStyle {}
+
+/// A greeting for a person, with the parts it was built from
+#[derive(Debug, Clone, PartialEq, Eq, Hash, uniffi::Object)]
+#[uniffi::export(Display, Eq, Hash)]
+pub struct Greeting(hello_rs::Greeting);
+
+#[uniffi::export]
+impl Greeting {
+    /// Greet the person called `name`, parsed with `locale`'s name order,
+    /// at `formality`
+    #[uniffi::constructor(default(bidi = None))]
+    pub fn for_person(
+        name: &str,
+        locale: &Locale,
+        formality: Formality,
+        how_are_you: bool,
+        bidi: Option<BidiIsolation>,
+    ) -> Self {
+        let mut builder = hello_rs::Greeting::for_person(PersonName::parse(name, locale))
+            .locale(locale.clone())
+            .formality(formality)
+            .how_are_you(how_are_you);
+        if let Some(bidi) = bidi {
+            builder = builder.bidi(bidi);
+        }
+        Greeting(builder.build())
+    }
+
+    /// The salutation, e.g. "good day"
+    pub fn salutation(&self) -> String {
+        self.0.salutation().into()
+    }
+
+    /// Who is being greeted, e.g. "Dr. Okafor"
+    pub fn recipient(&self) -> String {
+        self.0.recipient().into()
+    }
+
+    /// How formal the greeting is
+    pub fn formality(&self) -> Formality {
+        self.0.formality()
+    }
+
+    /// The locale used to translate the salutation
+    pub fn locale(&self) -> Arc<Locale> {
+        Arc::new(self.0.locale().clone())
+    }
+
+    /// The greeting rendered in `format`
+    pub fn render(&self, format: Format) -> String {
+        format.render(&self.0)
+    }
+}
+
+impl fmt::Display for Greeting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_hello() {
+        assert_eq!(try_hello("ana"), Ok("hello ana".to_string()));
+        assert_eq!(
+            try_hello("ana\nbo"),
+            Err(HelloError::ControlCharacters { offset: 3 })
+        );
+        assert_eq!(
+            try_hello("p\u{0430}ypal").unwrap_err().to_string(),
+            "name mixes Latin and Cyrillic scripts"
+        );
+    }
+
+    #[test]
+    fn test_hello_all() {
+        let names = vec!["Ana".to_string(), "Bo".to_string()];
+        assert_eq!(hello_all(names.clone(), None), "hello Ana and Bo");
+        let fr = Arc::new(Locale::parse("fr").unwrap());
+        assert_eq!(hello_all(names, Some(fr)), "bonjour Ana et Bo");
+        let pt_br = Locale::parse("pt-BR").unwrap();
+        assert!(supported_locales().iter().any(|l| **l == pt_br));
+    }
+
+    #[test]
+    fn test_style_registry() {
+        let styles = StyleRegistry::new();
+        let de = Locale::parse("de").unwrap();
+        assert_eq!(styles.greet("shout", &de, "ana").unwrap(), "HALLO ANA!");
+        assert_eq!(
+            styles.greet("whisper", &de, "ana").unwrap_err().to_string(),
+            r#"unknown greeting style: "whisper""#
+        );
+        assert_eq!(styles.names().len(), 8);
+    }
+
+    #[test]
+    fn test_greeting() {
+        let pt_br = Locale::parse("pt-BR").unwrap();
+        let greeting =
+            Greeting::for_person("Dr. Chidi Okafor", &pt_br, Formality::Formal, true, None);
+        assert_eq!(greeting.salutation(), "cumprimentos");
+        assert_eq!(greeting.recipient(), "Dr. Okafor");
+        assert_eq!(greeting.formality(), Formality::Formal);
+        assert_eq!(*greeting.locale(), pt_br);
+        assert_eq!(
+            greeting.render(Format::Plain),
+            "cumprimentos, Dr. Okafor, como o senhor está?"
+        );
+
+        let strip = BidiIsolation::new().strip_controls(true);
+        let greeting = Greeting::for_person(
+            "ana\u{202E}bo",
+            &pt_br,
+            Formality::Neutral,
+            false,
+            Some(strip),
+        );
+        assert_eq!(greeting.to_string(), "olá anabo");
+    }
+}
@@ -0,0 +1,123 @@
+// Exercises the generated Kotlin bindings on the JVM, the way an Android or
+// server-side Kotlin app would. Built and run by the kotlin-test check in
+// flake.nix:
+//
+//   uniffi-bindgen generate --library libhello_uniffi.so --language kotlin --config uniffi.toml --out-dir out
+//   kotlinc tests/HelloTest.kt out/uniffi/hello/hello.kt out/uniffi/hello_uniffi/hello_uniffi.kt \
+//     -cp jna.jar -include-runtime -d hello-test.jar
+//   java -Djna.library.path=target/release -cp hello-test.jar:jna.jar HelloTestKt
+//
+// HELLO_RS_VECTORS_TSV may name hello-rs's golden vectors as tab-separated
+// name, locale, style and expected greeting, which are replayed as well.
+
+import uniffi.hello.*
+import uniffi.hello_uniffi.*
+import java.io.File
+
+var failures = 0
+
+fun expect(test: String, expected: Any?, actual: () -> Any?) {
+    try {
+        val result = actual()
+        if (result == expected) {
+            println("ok   $test")
+        } else {
+            println("FAIL $test: expected \"$expected\", got \"$result\"")
+            failures++
+        }
+    } catch (e: Exception) {
+        println("FAIL $test: ${e::class.simpleName} ${e.message}")
+        failures++
+    }
+}
+
+inline fun <reified E : Exception> expectError(test: String, check: (E) -> Boolean = { true }, call: () -> Any?) {
+    try {
+        val result = call()
+        println("FAIL $test: expected ${E::class.simpleName}, got \"$result\"")
+        failures++
+    } catch (e: Exception) {
+        if (e is E && check(e)) {
+            println("ok   $test")
+        } else {
+            println("FAIL $test: expected ${E::class.simpleName}, got ${e::class.simpleName} ${e.message}")
+            failures++
+        }
+    }
+}
+
+fun main() {
+    val en = Locale.parse("en")
+    val de = Locale.parse("de")
+    val ptBr = Locale.parse("pt-BR")
+
+    expect("hello", "hello world") { hello("world") }
+    expect("tryHello", "hello ana") { tryHello("ana") }
+    expect("helloIn", "olá ana") { helloIn(ptBr, "ana") }
+    expect("helloAll", "hello ana, bo, and cy") { helloAll(listOf("ana", "bo", "cy")) }
+    expect("helloAll_locale", "hallo ana und bo") { helloAll(listOf("ana", "bo"), de) }
+
+    expectError<HelloException.Empty>("tryHello_empty") { tryHello(" ") }
+    expectError<HelloException.ControlCharacters>("tryHello_control", { it.offset == 3UL }) {
+        tryHello("ana\nbo")
+    }
+    expectError<HelloException.UnsafeBidi>("tryHello_bidi") { tryHello("ana\u202Ebo") }
+    expectError<HelloException.TooLong>("tryHello_too_long", { it.length == 1000UL }) {
+        tryHello("a".repeat(1000))
+    }
+    expectError<HelloException.InvalidScriptMix>(
+        "tryHello_script_mix",
+        { it.first == Script.LATIN && it.second == Script.CYRILLIC },
+    ) {
+        tryHello("p\u0430ypal")
+    }
+    expectError<LocaleException>("parse_bad_locale") { Locale.parse("not a tag") }
+
+    expect("locale", "pt-BR") { ptBr.toString() }
+    expect("locale_eq", Locale.parse("pt-BR")) { Locale.parse("pt_br") }
+    expect("locale_supported", false) { Locale.parse("pt-PT").isSupported() }
+    expect("supportedLocales", true) { ptBr in supportedLocales() }
+
+    val styles = StyleRegistry()
+    expect("greet", "Hello Anne-Marie van der Berg!") {
+        styles.greet("fancy", en, "anne-marie van der berg")
+    }
+    expect("greet_locale", "HALLO ANA!") { styles.greet("shout", de, "ana") }
+    expectError<UnknownStyle>("greet_unknown_style", { it.toString().contains("whisper") }) {
+        styles.greet("whisper", en, "ana")
+    }
+    expect("styles", listOf("plain", "fancy", "formal", "shout", "varied", "given", "family", "full")) {
+        styles.names()
+    }
+    expect("titleCase", "İstanbul") { TitleCase.forLocale(Locale.parse("tr")).apply("istanbul") }
+    expect("titleCase_default", "O'Neill van der Berg") { TitleCase().apply("o'neill van der berg") }
+
+    val formal = Greeting.forPerson("Dr. Chidi Okafor", ptBr, Formality.FORMAL, false)
+    expect("greeting", "cumprimentos, Dr. Okafor") { formal.toString() }
+    expect("greeting_salutation", "cumprimentos") { formal.salutation() }
+    expect("greeting_recipient", "Dr. Okafor") { formal.recipient() }
+    expect("greeting_locale", ptBr) { formal.locale() }
+    expect("greeting_formality", Formality.FORMAL) { formal.formality() }
+    expect("greeting_how_are_you", "good day, Dr. Okafor, how are you?") {
+        Greeting.forPerson("Dr. Chidi Okafor", en, Formality.FORMAL, true).toString()
+    }
+    expect("greeting_html", "<span lang=\"en\">hello <bdi>ana</bdi></span>") {
+        Greeting.forPerson("ana", en, Formality.NEUTRAL, false).render(Format.HTML)
+    }
+    expect("greeting_strip_bidi", "hello anabo") {
+        Greeting.forPerson("ana\u202Ebo", en, Formality.NEUTRAL, false, BidiIsolation(stripControls = true))
+            .toString()
+    }
+
+    System.getenv("HELLO_RS_VECTORS_TSV")?.let { path ->
+        File(path).readLines().filter { it.isNotEmpty() }.forEach { line ->
+            val (name, locale, style, expected) = line.split('\t')
+            expect("vector $style $locale \"$name\"", expected) {
+                styles.greet(style, Locale.parse(locale), name)
+            }
+        }
+    }
+
+    println(if (failures == 0) "all tests passed" else "some tests failed")
+    kotlin.system.exitProcess(if (failures == 0) 0 else 1)
+}
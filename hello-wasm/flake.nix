@@ -32,21 +32,23 @@
           targets = [ "wasm32-wasip2" ];
         };
 
+        # Create source with hello-rs included from non-flake input
+        helloWasmSrc = pkgs.runCommand "hello-wasm-src" {} ''
+          mkdir -p $out
+          cp -r ${./.}/* $out/
+          chmod -R +w $out
+          mkdir -p $out/hello-rs
+          cp -r ${hello-rs}/* $out/hello-rs/
+          # Update the Cargo.toml path to point to ./hello-rs instead of ../hello-rs
+          sed -i 's|path = "../hello-rs"|path = "./hello-rs"|' $out/Cargo.toml
+        '';
+
         # Build the WASM component
         helloWasmComponent = pkgs.stdenv.mkDerivation {
           pname = "hello-wasm";
           version = "0.1.0";
 
-          # Create source with hello-rs included from non-flake input
-          src = pkgs.runCommand "hello-wasm-src" {} ''
-            mkdir -p $out
-            cp -r ${./.}/* $out/
-            chmod -R +w $out
-            mkdir -p $out/hello-rs
-            cp -r ${hello-rs}/* $out/hello-rs/
-            # Update the Cargo.toml path to point to ./hello-rs instead of ../hello-rs
-            sed -i 's|path = "../hello-rs"|path = "./hello-rs"|' $out/Cargo.toml
-          '';
+          src = helloWasmSrc;
 
           cargoDeps = pkgs.rustPlatform.importCargoLock {
             lockFile = ./Cargo.lock;
@@ -81,6 +83,34 @@
         };
 
         checks = {
+          # Run hello-rs's contract against the hello-rs this component is
+          # built with, as a wasm32-wasip2 test binary under wasmtime
+          contract = pkgs.stdenv.mkDerivation {
+            name = "hello-wasm-contract";
+            src = helloWasmSrc;
+
+            cargoDeps = pkgs.rustPlatform.importCargoLock {
+              lockFile = ./Cargo.lock;
+            };
+
+            nativeBuildInputs = with pkgs; [
+              rustToolchain
+              rustPlatform.cargoSetupHook
+              wasmtime
+            ];
+
+            buildPhase = ''
+              export HOME=$TMPDIR
+              export CARGO_TARGET_WASM32_WASIP2_RUNNER=wasmtime
+              cargo test --target wasm32-wasip2 --test contract 2>&1 | tee test-output.txt
+            '';
+
+            installPhase = ''
+              mkdir -p $out
+              cp test-output.txt $out/
+            '';
+          };
+
           wasmtime-test = pkgs.stdenv.mkDerivation {
             name = "hello-wasm-wasmtime-test";
             src = ./.;
@@ -113,16 +143,17 @@
               TEST_RESULTS=""
 
               # Helper function to run a test
+              # Takes a test name, a WAVE-encoded invocation, and the expected output
               run_test() {
                 local test_name="$1"
-                local input="$2"
+                local invocation="$2"
                 local expected="$3"
 
                 TOTAL=$((TOTAL + 1))
 
                 # Run wasmtime with cache disabled for hermetic builds
                 # For Component Model, use simple function invocation syntax
-                if output=$(wasmtime run -C cache=n --invoke 'hello("'"$input"'")' hello_wasm.wasm 2>&1); then
+                if output=$(wasmtime run -C cache=n --invoke "$invocation" hello_wasm.wasm 2>&1); then
                   # Strip any ANSI codes and trim whitespace
                   output=$(echo "$output" | sed 's/\x1b\[[0-9;]*m//g' | xargs)
 
@@ -142,11 +173,117 @@
                 fi
               }
 
+              # Helper function for tests whose output depends on the host,
+              # e.g. the wall clock. Takes a test name, a WAVE-encoded
+              # invocation, and an extended regex the output must match.
+              # The output is matched as WAVE prints it, quotes included,
+              # since xargs can't unquote strings with escaped quotes
+              run_test_matching() {
+                local test_name="$1"
+                local invocation="$2"
+                local pattern="$3"
+
+                TOTAL=$((TOTAL + 1))
+
+                if output=$(wasmtime run -C cache=n --invoke "$invocation" hello_wasm.wasm 2>&1); then
+                  output=$(echo "$output" | sed -e 's/\x1b\[[0-9;]*m//g' -e 's/^[[:space:]]*//' -e 's/[[:space:]]*$//')
+
+                  if echo "$output" | grep -Eq "$pattern"; then
+                    PASSED=$((PASSED + 1))
+                    TEST_RESULTS="$TEST_RESULTS  [passed] $test_name\n"
+                    echo "✓ $test_name: '$output'"
+                  else
+                    FAILED=$((FAILED + 1))
+                    TEST_RESULTS="$TEST_RESULTS  [failed] $test_name (expected match for: '$pattern', got: '$output')\n"
+                    echo "✗ $test_name: expected match for '$pattern', got '$output'"
+                  fi
+                else
+                  FAILED=$((FAILED + 1))
+                  TEST_RESULTS="$TEST_RESULTS  [failed] $test_name (wasmtime error)\n"
+                  echo "✗ $test_name: wasmtime failed with: $output"
+                fi
+              }
+
+              # Helper function for hello-rs's golden vectors. Takes a test
+              # name, a WAVE-encoded invocation, and the exact output as WAVE
+              # prints it, quotes included
+              run_test_exact() {
+                local test_name="$1"
+                local invocation="$2"
+                local expected="$3"
+
+                TOTAL=$((TOTAL + 1))
+
+                if output=$(wasmtime run -C cache=n --invoke "$invocation" hello_wasm.wasm 2>&1); then
+                  output=$(echo "$output" | sed -e 's/^[[:space:]]*//' -e 's/[[:space:]]*$//')
+
+                  if [ "$output" = "$expected" ]; then
+                    PASSED=$((PASSED + 1))
+                    TEST_RESULTS="$TEST_RESULTS  [passed] $test_name\n"
+                    echo "✓ $test_name: '$output'"
+                  else
+                    FAILED=$((FAILED + 1))
+                    TEST_RESULTS="$TEST_RESULTS  [failed] $test_name (expected: '$expected', got: '$output')\n"
+                    echo "✗ $test_name: expected '$expected', got '$output'"
+                  fi
+                else
+                  FAILED=$((FAILED + 1))
+                  TEST_RESULTS="$TEST_RESULTS  [failed] $test_name (wasmtime error)\n"
+                  echo "✗ $test_name: wasmtime failed with: $output"
+                fi
+              }
+
               # Run test cases
               echo "Running wasmtime tests..."
-              run_test "test_hello_basic" "world" "Hello World!"
-              run_test "test_hello_name" "claude" "Hello Claude!"
-              run_test "test_hello_multiword" "rust wasm" "Hello Rust Wasm!"
+              run_test "test_hello_basic" 'hello("world")' "Hello World!"
+              run_test "test_hello_name" 'hello("claude")' "Hello Claude!"
+              run_test "test_hello_multiword" 'hello("rust wasm")' "Hello Rust Wasm!"
+              run_test "test_hello_particles" 'hello("anne-marie van der berg")' "Hello Anne-Marie van der Berg!"
+              run_test "test_hello_all" 'hello-all(["ana", "bo", "cy"])' "Hello Ana, Bo, and Cy!"
+              run_test "test_try_hello" 'try-hello("world")' "ok(Hello World!)"
+              run_test "test_try_hello_empty" 'try-hello("")' "err(empty)"
+              run_test "test_try_hello_too_long" "try-hello(\"$(printf 'a%.0s' {1..200})\")" "err(too-long(128))"
+              run_test "test_try_hello_control" 'try-hello("ana\nbo")' "err(control-characters)"
+              run_test "test_try_hello_bidi" 'try-hello("ana\u{202e}bo")' "err(unsafe-bidi)"
+              run_test "test_try_hello_script_mix" 'try-hello("p\u{430}ypal")' "err(invalid-script-mix)"
+              run_test "test_hello_in" 'hello-in("es", "mundo")' "ok(Hola Mundo!)"
+              run_test "test_hello_in_fallback" 'hello-in("pt-BR", "ana")' "ok(Olá Ana!)"
+              run_test_matching "test_hello_now" 'hello-now("en", "ana", 0)' '^ok\("(Good (Morning|Afternoon|Evening)|Happy (New Year|Easter)|Merry Christmas) Ana!"\)$'
+              run_test_matching "test_hello_now_bad_locale" 'hello-now("not a locale", "ana", 0)' '^err\("invalid locale tag'
+              run_test "test_hello_with_template" 'hello-with-template("{salutation|title}, {name|upper}{punct}", "ana")' "ok(Hello, ANA!)"
+              run_test "test_hello_with_template_error" 'hello-with-template("hello {name", "ana")' "err({message: unclosed placeholder at byte 6, offset: 6})"
+              run_test "test_hello_with_formality" 'hello-with-formality("dr. chidi okafor", formal, "en", plain)' "ok(Good Day, Dr. Okafor)"
+              run_test "test_hello_with_formality_casual" 'hello-with-formality("dr. chidi okafor", casual, "de", plain)' "ok(Hi Chidi)"
+              run_test_matching "test_hello_with_formality_html" 'hello-with-formality("<chidi>", neutral, "en", html)' '^ok\("<span lang=\\"en\\">Hello <bdi>&lt;Chidi&gt;</bdi></span>"\)$'
+              run_test "test_hello_with_formality_markdown" 'hello-with-formality("chidi", formal, "en", markdown)' "ok(Good Day, **Chidi**)"
+              run_test "test_hello_with_bidi" 'hello-with-bidi("ana", {isolate, strip-controls})' "Hello Ana!"
+              run_test_matching "test_hello_with_bidi_rtl" 'hello-with-bidi("שרה", {isolate})' '^"Hello .+שרה.+!"$'
+              run_test "test_hello_with_config" 'hello-with-config("frau anna schmidt", "{\"locale\": \"de\", \"formality\": \"formal\"}")' "ok(guten Tag, frau schmidt)"
+              run_test_matching "test_hello_with_config_error" 'hello-with-config("ana", "{\"formalty\": \"formal\"}")' '^err\("unknown field'
+              run_test "test_hello_varied" 'hello-varied("ana", none)' "greetings ana"
+              run_test "test_hello_varied_seed" 'hello-varied("ana", some(0))' "hi ana"
+              run_test "test_greet_plain" 'greet("plain", "ana")' "ok(hello ana)"
+              run_test "test_greet_formal" 'greet("formal", "okafor")' "ok(Good Day, Okafor.)"
+              run_test "test_greet_shout" 'greet("shout", "ana")' "ok(HELLO ANA!)"
+              run_test "test_greet_in" 'greet-in("shout", "de", "ana")' "ok(HALLO ANA!)"
+              run_test_matching "test_greet_in_bad_locale" 'greet-in("plain", "not a locale", "ana")' '^err\("invalid locale tag'
+              run_test_matching "test_greet_unknown" 'greet("whisper", "ana")' '^err\("unknown greeting style'
+              run_test "test_greet_family" 'greet("family", "dr. jane q. public")' "ok(Good Day, Dr. Public.)"
+              run_test "test_styles" 'styles()' "[plain, fancy, formal, shout, varied, given, family, full]"
+              run_test "test_supported_locales" 'supported-locales()' "[en, de, es, fr, it, nl, pt, pt-BR, tr]"
+
+              # Replay hello-rs's golden vectors, shared with hello-rs's and
+              # hello-py's tests. JSON strings without control characters
+              # are also valid WAVE strings, so jq can write the invocations
+              VECTORS=${hello-rs}/vectors/greetings.json
+              [ "$(jq .version "$VECTORS")" = 1 ]
+              while IFS=$'\t' read -r test_name invocation expected; do
+                run_test_exact "$test_name" "$invocation" "$expected"
+              done < <(jq -r '.vectors[] | [
+                "vector_\(.style)_\(.locale)_\(.name)",
+                "greet-in(\(.style | tojson), \(.locale | tojson), \(.name | tojson))",
+                "ok(\(.expected | tojson))"
+              ] | @tsv' "$VECTORS")
 
               # Print summary
               echo ""
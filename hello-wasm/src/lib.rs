@@ -1,46 +1,259 @@
-/// WebAssembly component wrapper for hello-rs
-///
-/// This component exposes the hello-rs library through the WebAssembly Component Model,
-/// making it callable from JavaScript in browsers.
+//! WebAssembly component wrapper for hello-rs
+//!
+//! This component exposes the hello-rs library through the WebAssembly Component Model,
+//! making it callable from JavaScript in browsers.
 
 mod bindings {
-    //! Generated bindings for the greeter world defined in wit/world.wit
+    //! Generated bindings for the greeter world defined in wit/world.wit,
+    //! with its WASI imports resolved from wit/deps
     wit_bindgen::generate!({
-        path: "wit/world.wit",
+        path: "wit",
+        world: "greeter-world",
+        generate_all,
     });
 }
 
+use std::cell::RefCell;
+
+use bindings::exports::example::greeter::greeter::{
+    Bidi, Formality, Format, HelloError, TemplateError,
+};
+use bindings::wasi::clocks::wall_clock;
+use hello_rs::format::TitleCase;
+use hello_rs::{
+    BidiIsolation, Capitalization, Clock, FancyGreeter, Greeter, Greeting, GreetingConfig,
+    ListFormat, Locale, PersonName, Render, StyleRegistry, Template, UtcOffset, VariedGreeter,
+    ZonedTime,
+};
+
+thread_local! {
+    /// Scratch space for the plain greeting, reused across calls so the
+    /// hot `hello` export only allocates the String it returns
+    static BUFFER: RefCell<String> = const { RefCell::new(String::new()) };
+
+    /// hello-rs's built-in greeting styles, built once per instance
+    static STYLES: StyleRegistry = StyleRegistry::default();
+}
+
 /// The GreeterComponent struct implements the greeter interface
 struct GreeterComponent;
 
 impl bindings::exports::example::greeter::greeter::Guest for GreeterComponent {
     /// Generate a formatted greeting for the given name
     ///
-    /// This calls the hello-rs library and then formats the output
-    /// by capitalizing each word and adding an exclamation mark.
+    /// This renders hello-rs's "fancy" style, which title-cases the
+    /// greeting and adds an exclamation mark.
     ///
     /// Example: "matt" -> "Hello Matt!"
     fn hello(name: String) -> String {
-        // Get the base greeting from hello-rs
-        let greeting = hello_rs::hello(&name);
-
-        // Format the greeting: capitalize each word and add "!"
-        let formatted = greeting
-            .split_whitespace()
-            .map(|word| {
-                let mut chars = word.chars();
-                match chars.next() {
-                    None => String::new(),
-                    Some(first) => {
-                        first.to_uppercase().collect::<String>() + chars.as_str()
-                    }
-                }
-            })
-            .collect::<Vec<_>>()
-            .join(" ");
-
-        format!("{}!", formatted)
+        BUFFER.with_borrow_mut(|buf| {
+            buf.clear();
+            FancyGreeter
+                .write_greeting(buf, &Locale::default(), &name)
+                .expect("writing to a String cannot fail");
+            buf.clone()
+        })
+    }
+
+    /// Generate a formatted greeting with a bidi-safe name
+    ///
+    /// Example: ("محمد", isolate) -> "Hello \u{2068}محمد\u{2069}!"
+    fn hello_with_bidi(name: String, bidi: Bidi) -> String {
+        let name = BidiIsolation::new()
+            .isolate(bidi.contains(Bidi::ISOLATE))
+            .strip_controls(bidi.contains(Bidi::STRIP_CONTROLS))
+            .apply(&name);
+        FancyGreeter.greet(&Locale::default(), &name)
+    }
+
+    /// Generate one formatted greeting for several names
+    ///
+    /// Each name is title-cased on its own so the list's conjunction stays
+    /// lowercase.
+    ///
+    /// Example: ["ana", "bo", "cy"] -> "Hello Ana, Bo, and Cy!"
+    fn hello_all(names: Vec<String>) -> String {
+        let title_case = TitleCase::new();
+        let names: Vec<String> = names.iter().map(|name| title_case.apply(name)).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+
+        let recipients = ListFormat::new(&Locale::default())
+            .max_items(hello_rs::DEFAULT_MAX_NAMES)
+            .format(&names);
+        Greeting::builder(recipients)
+            .capitalization(Capitalization::Sentence)
+            .punctuation("!")
+            .build()
+            .to_string()
+    }
+
+    /// Generate a formatted greeting after validating the name
+    ///
+    /// Example: "" -> Err(HelloError::Empty)
+    fn try_hello(name: String) -> Result<String, HelloError> {
+        hello_rs::NamePolicy::default()
+            .validate(&name)
+            .map_err(hello_error)?;
+        Ok(FancyGreeter.greet(&Locale::default(), &name))
+    }
+
+    /// Generate a formatted greeting in the given locale
+    ///
+    /// Example: ("es", "matt") -> "Hola Matt!"
+    fn hello_in(locale: String, name: String) -> Result<String, String> {
+        let locale = Locale::parse(&locale).map_err(|e| e.to_string())?;
+        Ok(FancyGreeter.greet(&locale, &name))
+    }
+
+    /// Generate a formatted greeting for the host's current time of day
+    ///
+    /// Example: ("en", "matt", 60) at 07:30 UTC -> "Good Morning Matt!"
+    fn hello_now(locale: String, name: String, utc_offset_minutes: i32) -> Result<String, String> {
+        let locale = Locale::parse(&locale).map_err(|e| e.to_string())?;
+        let clock = WasiClock(UtcOffset::from_minutes(utc_offset_minutes));
+        Ok(fancy(&hello_rs::hello_now(&clock, &locale, &name), &locale))
+    }
+
+    /// Generate a greeting from a user-supplied template
+    ///
+    /// Example: ("{salutation|title}, {name|upper}{punct}", "matt") -> "Hello, MATT!"
+    fn hello_with_template(template: String, name: String) -> Result<String, TemplateError> {
+        let template = Template::compile(&template).map_err(|e| TemplateError {
+            message: e.to_string(),
+            offset: e.offset().try_into().unwrap_or(u32::MAX),
+        })?;
+        Ok(template.render(&Greeting::builder(name).punctuation("!").build()))
+    }
+
+    /// Generate a title-cased greeting at the given formality
+    ///
+    /// Example: ("dr. chidi okafor", formal, "en", plain) -> "Good Day, Dr. Okafor"
+    fn hello_with_formality(
+        name: String,
+        formality: Formality,
+        locale: String,
+        format: Format,
+    ) -> Result<String, String> {
+        let locale = Locale::parse(&locale).map_err(|e| e.to_string())?;
+        let person = PersonName::parse(&name, &locale);
+        let greeting = Greeting::for_person(person)
+            .formality(formality_level(formality))
+            .capitalization(Capitalization::Title)
+            .locale(locale)
+            .build();
+        Ok(renderer(format).render(&greeting))
+    }
+
+    /// Generate a greeting from a JSON config
+    ///
+    /// Example: ("ana", r#"{"formality": "casual"}"#) -> "hey ana"
+    fn hello_with_config(name: String, config: String) -> Result<String, String> {
+        let config: GreetingConfig = serde_json::from_str(&config).map_err(|e| e.to_string())?;
+        Ok(config.greet(&name))
+    }
+
+    /// Generate a greeting with a seeded or name-keyed salutation variant
+    ///
+    /// Example: ("ana", Some(0)) -> "hi ana"
+    fn hello_varied(name: String, seed: Option<u64>) -> String {
+        let greeter = match seed {
+            Some(seed) => VariedGreeter::with_seed(seed),
+            None => VariedGreeter::new(),
+        };
+        greeter.greet(&Locale::default(), &name)
+    }
+
+    /// Generate a greeting in one of hello-rs's named styles
+    ///
+    /// Example: ("shout", "matt") -> "HELLO MATT!"
+    fn greet(style: String, name: String) -> Result<String, String> {
+        STYLES.with(|styles| {
+            styles
+                .greet(&style, &Locale::default(), &name)
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    /// Generate a greeting in one of hello-rs's named styles and a locale
+    ///
+    /// Example: ("shout", "de", "matt") -> "HALLO MATT!"
+    fn greet_in(style: String, locale: String, name: String) -> Result<String, String> {
+        let locale = Locale::parse(&locale).map_err(|e| e.to_string())?;
+        STYLES.with(|styles| {
+            styles
+                .greet(&style, &locale, &name)
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    /// List the styles `greet` accepts
+    fn styles() -> Vec<String> {
+        STYLES.with(|styles| styles.names().map(str::to_string).collect())
     }
+
+    /// List the locales hello-rs has translations for
+    fn supported_locales() -> Vec<String> {
+        hello_rs::supported_locales()
+            .map(|locale| locale.to_string())
+            .collect()
+    }
+}
+
+/// A clock backed by the host's wasi:clocks wall clock, read at a fixed
+/// UTC offset
+struct WasiClock(UtcOffset);
+
+impl Clock for WasiClock {
+    fn now(&self) -> ZonedTime {
+        let seconds = wall_clock::now().seconds;
+        ZonedTime::new(seconds.try_into().unwrap_or(i64::MAX), self.0)
+    }
+}
+
+/// Convert a hello-rs error into its WIT counterpart
+fn hello_error(error: hello_rs::HelloError) -> HelloError {
+    match error {
+        hello_rs::HelloError::Empty => HelloError::Empty,
+        hello_rs::HelloError::TooLong { max, .. } => {
+            HelloError::TooLong(max.try_into().unwrap_or(u32::MAX))
+        }
+        hello_rs::HelloError::ControlCharacters { .. } => HelloError::ControlCharacters,
+        hello_rs::HelloError::UnsafeBidi { .. } => HelloError::UnsafeBidi,
+        hello_rs::HelloError::InvalidScriptMix { .. } => HelloError::InvalidScriptMix,
+    }
+}
+
+/// Convert a WIT formality into its hello-rs counterpart
+fn formality_level(formality: Formality) -> hello_rs::Formality {
+    match formality {
+        Formality::Intimate => hello_rs::Formality::Intimate,
+        Formality::Casual => hello_rs::Formality::Casual,
+        Formality::Neutral => hello_rs::Formality::Neutral,
+        Formality::Formal => hello_rs::Formality::Formal,
+        Formality::Ceremonial => hello_rs::Formality::Ceremonial,
+    }
+}
+
+/// Convert a WIT format into its hello-rs counterpart
+fn renderer(format: Format) -> hello_rs::Format {
+    match format {
+        Format::Plain => hello_rs::Format::Plain,
+        Format::Html => hello_rs::Format::Html,
+        Format::Markdown => hello_rs::Format::Markdown,
+        Format::Ansi => hello_rs::Format::Ansi,
+        Format::Json => hello_rs::Format::Json,
+    }
+}
+
+/// Format a greeting: collapse whitespace, title-case it and add "!"
+///
+/// Title-casing is delegated to hello-rs so that names like "o'neill" and
+/// "van der berg" come out the same here as in every other binding.
+fn fancy(greeting: &str, locale: &Locale) -> String {
+    let words = greeting.split_whitespace().collect::<Vec<_>>().join(" ");
+    let formatted = TitleCase::for_locale(locale).apply(&words);
+
+    format!("{}!", formatted)
 }
 
 // Export the component using the generated macro
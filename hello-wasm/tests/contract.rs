@@ -0,0 +1,10 @@
+//! Runs hello-rs's contract against the build of hello-rs this crate links.
+//!
+//! hello-wasm capitalizes and punctuates the lowercase "hello {name}" that
+//! hello-rs returns, so a hello-rs release that changes the format should
+//! fail here, not in the browser.
+
+#[test]
+fn test_hello_rs_contract() {
+    hello_rs::contract::verify(hello_rs::hello);
+}
@@ -1,7 +1,7 @@
 """CLI app for hello-fancy."""
 
 import typer
-from hello_py import hello
+from hello_py import greet
 
 app = typer.Typer()
 
@@ -14,16 +14,8 @@ def main(name: str) -> None:
     Args:
         name: The name of the person to greet
     """
-    # Get the greeting from the Rust-backed hello_py library
-    greeting = hello(name)
-
-    # Make it fancy: capitalize the name and add exclamation
-    parts = greeting.split()
-    if len(parts) >= 2:
-        fancy_greeting = f"{parts[0].capitalize()} {parts[1].capitalize()}!"
-        typer.echo(fancy_greeting)
-    else:
-        typer.echo(greeting)
+    # hello-rs's "fancy" style title-cases the greeting and adds an exclamation
+    typer.echo(greet(name, "fancy"))
 
 
 if __name__ == "__main__":